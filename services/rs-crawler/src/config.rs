@@ -14,6 +14,31 @@ pub const RETRY_MAX_ATTEMPTS: u8 = 4;
 pub const RETRY_BASE_BACKOFF: u64 = 250;
 pub const RETRY_JITTER: bool = true;
 pub const RETRYABLE_STATUSES: [u16; 5] = [429, 500, 502, 503, 504];
+pub const RETRY_AFTER_CAP_SECS: u64 = 120;
+
+// Fallback token-bucket rate for clients with no published per-provider
+// limit of their own (e.g. Invidious).
+pub const DEFAULT_MAX_RPS: f32 = 5.0;
+pub const DEFAULT_BURST: u32 = 5;
+
+/// Token-bucket ceiling for a single provider: `max_rps` tokens refill per
+/// second up to `burst` capacity. Shared across every provider config so
+/// the fetch layer can build one `RateLimiter` type regardless of provider.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    pub max_rps: f32,
+    pub burst: u32,
+}
+
+fn env_rate_limit(rps_var: &str, burst_var: &str, default: RateLimitConfig) -> RateLimitConfig {
+    let max_rps = std::env::var(rps_var).ok()
+        .and_then(|s| s.parse::<f32>().ok())
+        .unwrap_or(default.max_rps);
+    let burst = std::env::var(burst_var).ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(default.burst);
+    RateLimitConfig { max_rps, burst }
+}
 
 /// Wrapper over env::var to return an invalid enviroment var error
 fn env_check(s: &str) -> Result<String, CrawlerError> {
@@ -61,13 +86,37 @@ fn build_identity() -> Result<IdentityConfig, CrawlerError> {
     Ok( IdentityConfig { app_env, mb_user_agent } )
 }
 
-/// Configuration that Spotify expects when hitting endpoints 
-#[derive(Debug, Clone)]
+const REDACTED: &str = "***redacted***";
+
+/// Configuration that Spotify expects when hitting endpoints
+#[derive(Clone)]
 pub struct SpotifyConfig {
-    pub client_id: String, 
-    pub client_secret: String, 
-    pub token_url: Url, 
-    pub api_base: Url, 
+    pub client_id: String,
+    pub client_secret: String,
+    pub token_url: Url,
+    pub api_base: Url,
+    /// Space-separated OAuth scopes; unused by the client-credentials grant
+    /// but threaded through so an authorization-code flow can be layered on
+    /// later without another config change.
+    pub scopes: Option<String>,
+    /// Safety margin subtracted from `expires_in` before a cached token is
+    /// treated as stale, so a token doesn't expire mid-request.
+    pub refresh_skew: time::Duration,
+    pub rate_limit: RateLimitConfig,
+}
+
+impl std::fmt::Debug for SpotifyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpotifyConfig")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &REDACTED)
+            .field("token_url", &self.token_url)
+            .field("api_base", &self.api_base)
+            .field("scopes", &self.scopes)
+            .field("refresh_skew", &self.refresh_skew)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
 }
 
 fn build_spotify() -> Result<SpotifyConfig, CrawlerError> {
@@ -100,12 +149,28 @@ fn build_spotify() -> Result<SpotifyConfig, CrawlerError> {
         .map_err(|e| CrawlerError::Config(e))?;
 
     if !api_base.path().ends_with('/') {
-        let mut path = api_base.path().to_string(); 
+        let mut path = api_base.path().to_string();
         path.push('/');
         api_base.set_path(&path);
     }
 
-    Ok( SpotifyConfig { client_id, client_secret, token_url, api_base })
+    let scopes = std::env::var("SPOTIFY_SCOPES").ok()
+        .filter(|s| !s.trim().is_empty());
+    let refresh_skew_secs = std::env::var("SPOTIFY_REFRESH_SKEW_SECS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(30);
+
+    let rate_limit = env_rate_limit(
+        "SPOTIFY_MAX_RPS", "SPOTIFY_BURST",
+        RateLimitConfig { max_rps: 10.0, burst: 20 },
+    );
+
+    Ok( SpotifyConfig {
+        client_id, client_secret, token_url, api_base,
+        scopes, refresh_skew: time::Duration::from_secs(refresh_skew_secs),
+        rate_limit,
+    })
 }
 
 /// 
@@ -117,9 +182,9 @@ pub struct MusicBrainzConfig {
     pub user_agent: String,    // app/version (ex@mail.com)
     pub inc_recording: String, // 
     pub search_limit: u32,     // default 5
-    pub search_offset: u32,    // default 0 
-    pub max_rps: f32,          // default 1.0 
-    pub duration_tol: u32      // default 1500 
+    pub search_offset: u32,    // default 0
+    pub rate_limit: RateLimitConfig, // default 1.0 rps / burst 1, per MB's politeness policy
+    pub duration_tol: u32      // default 1500
 }
 
 fn build_musicbrainz(identity: &IdentityConfig) -> 
@@ -181,7 +246,10 @@ fn build_musicbrainz(identity: &IdentityConfig) ->
         .unwrap_or_else(|_| "artist-credits+isrcs+releases".to_string());
     let search_limit  = env_to_uint("MB_SEARCH_LIMIT", 5);
     let search_offset = env_to_uint("MB_SEARCH_OFFSET", 0);
-    let max_rps       = env_to_float("MB_MAX_RPS", 1.0);
+    let rate_limit    = RateLimitConfig {
+        max_rps: env_to_float("MB_MAX_RPS", 1.0),
+        burst: env_to_uint("MB_BURST", 1),
+    };
     let duration_tol  = env_to_uint("MB_SEARCH_DURATION_TOL", 1500);
 
     Ok( MusicBrainzConfig {
@@ -190,23 +258,40 @@ fn build_musicbrainz(identity: &IdentityConfig) ->
         inc_recording,
         search_limit,
         search_offset,
-        max_rps,
+        rate_limit,
         duration_tol,
     })
 }   
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct AcoustIdConfig {
-    pub api_key: String, 
-    pub base_url: Url, 
-    pub meta: String 
+    pub api_key: String,
+    pub base_url: Url,
+    pub meta: String,
+    pub rate_limit: RateLimitConfig,
+}
+
+impl std::fmt::Debug for AcoustIdConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcoustIdConfig")
+            .field("api_key", &REDACTED)
+            .field("base_url", &self.base_url)
+            .field("meta", &self.meta)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+fn default_acoustid_rate_limit() -> RateLimitConfig {
+    // AcoustId's API guidelines ask integrators to stay near 3 req/s.
+    RateLimitConfig { max_rps: 3.0, burst: 3 }
 }
 
 fn build_acoustid() -> Result<AcoustIdConfig, CrawlerError> {
     let api_key = env_check("ACOUST_ID")?;
 
     let base_url = std::env::var("ACOUST_BASE_URL")
-        .unwrap_or_else(|_| "https:/api.acoustid.org/v2/".to_string());
+        .unwrap_or_else(|_| "https://api.acoustid.org/v2/".to_string());
     let mut base_url = Url::parse(&base_url)
         .map_err(|e| CrawlerError::Config(
             format!("ACOUST_BASE_URL invalid {e}")
@@ -225,44 +310,56 @@ fn build_acoustid() -> Result<AcoustIdConfig, CrawlerError> {
     }
 
     let meta = std::env::var("ACOUSTID_META")
-        .unwrap_or_else(|_| 
+        .unwrap_or_else(|_|
             "recordings+recordingids+releaseids+tracks+compress".to_string()
         );
+    let rate_limit = env_rate_limit(
+        "ACOUSTID_MAX_RPS", "ACOUSTID_BURST", default_acoustid_rate_limit()
+    );
 
-    Ok( AcoustIdConfig { api_key, base_url, meta } )
+    Ok( AcoustIdConfig { api_key, base_url, meta, rate_limit } )
 }
 
 /// 
 /// Configuration for Http timeouts, retries, etc. 
 ///
 #[derive(Debug, Clone)]
-pub struct RetryConfig { 
-    pub max_attempts: u8, 
-    pub base_backoff: time::Duration, 
-    pub jitter: bool, 
-    pub retryable_statuses: Vec<u16> 
+pub struct RetryConfig {
+    pub max_attempts: u8,
+    pub base_backoff: time::Duration,
+    pub jitter: bool,
+    pub retryable_statuses: Vec<u16>,
+    /// Prefer a server-sent `Retry-After` over computed backoff when present.
+    pub respect_retry_after: bool,
+    /// Upper bound applied to a `Retry-After` value before sleeping on it,
+    /// so a misbehaving server can't stall the pipeline indefinitely.
+    pub retry_after_cap: time::Duration,
 }
 
 impl Default for RetryConfig {
     fn default() -> Self {
         Self {
-            max_attempts: RETRY_MAX_ATTEMPTS, 
+            max_attempts: RETRY_MAX_ATTEMPTS,
             base_backoff: time::Duration::from_millis(RETRY_BASE_BACKOFF),
-            jitter: RETRY_JITTER, 
-            retryable_statuses: RETRYABLE_STATUSES.to_vec()
+            jitter: RETRY_JITTER,
+            retryable_statuses: RETRYABLE_STATUSES.to_vec(),
+            respect_retry_after: true,
+            retry_after_cap: time::Duration::from_secs(RETRY_AFTER_CAP_SECS),
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct HttpConfig {
-    pub timeout: time::Duration, 
-    pub connect_timeout: time::Duration, 
-    pub pool_max_idle_per_host: usize, 
-    pub pool_idle_timeout: time::Duration, 
-    pub max_redirects: u8, 
-    pub retry: RetryConfig
-} 
+    pub timeout: time::Duration,
+    pub connect_timeout: time::Duration,
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout: time::Duration,
+    pub max_redirects: u8,
+    pub retry: RetryConfig,
+    pub default_max_rps: f32,
+    pub default_burst: u32,
+}
 
 impl Default for HttpConfig {
     fn default() -> Self {
@@ -271,8 +368,10 @@ impl Default for HttpConfig {
             connect_timeout: time::Duration::from_millis(HTTP_CONNECT_TIMEOUT),
             pool_max_idle_per_host: HTTP_POOL_MAX_IDLE,
             pool_idle_timeout: time::Duration::from_millis(HTTP_POOL_IDLE_TIMEOUT),
-            max_redirects: HTTP_MAX_REDIRECTS, 
-            retry: RetryConfig::default()
+            max_redirects: HTTP_MAX_REDIRECTS,
+            retry: RetryConfig::default(),
+            default_max_rps: DEFAULT_MAX_RPS,
+            default_burst: DEFAULT_BURST,
         }
     }
 }
@@ -336,8 +435,12 @@ pub struct MatchingConfig {
     pub duration_tol: u32,       // allowable difference on duration
     pub require_isrc_echo: bool, // if you started with an ISRC, must MB echo it?
     pub prefer_same_isrc: bool,  // if not required, still bonus matching ISRC
-    pub title_norm: TitleNorm,   // how to normalize similar titles  
+    pub title_norm: TitleNorm,   // how to normalize similar titles
     pub ambiguity_margin: f32,   // top1 - top2 composite gap to auto-accept
+    /// When the top two candidates fall within `ambiguity_margin`, let an
+    /// AcoustId fingerprint match on recording id break the tie instead of
+    /// dropping the row as ambiguous.
+    pub use_acoustid_tiebreak: bool,
 }
 
 impl Default for MatchingConfig {
@@ -349,6 +452,7 @@ impl Default for MatchingConfig {
             prefer_same_isrc: true,
             title_norm: TitleNorm::AsciiFoldLowerTrim,
             ambiguity_margin: 0.05,
+            use_acoustid_tiebreak: true,
         }
     }
 }
@@ -438,21 +542,46 @@ impl Default for LoggingConfig {
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct AcousticBrainzConfig {
-    pub base_url: String 
+    pub base_url: String,
+    #[serde(skip, default = "default_acousticbrainz_rate_limit")]
+    pub rate_limit: RateLimitConfig,
+}
+
+fn default_acousticbrainz_rate_limit() -> RateLimitConfig {
+    RateLimitConfig { max_rps: 1.0, burst: 1 }
 }
 
 impl Default for AcousticBrainzConfig {
     fn default() -> Self {
         Self {
-            base_url: "https://acousticbrainz.org/".to_string(), 
+            base_url: "https://acousticbrainz.org/".to_string(),
+            rate_limit: env_rate_limit(
+                "AB_MAX_RPS", "AB_BURST", default_acousticbrainz_rate_limit()
+            ),
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct LastFmConfig {
-    pub base_url: String, 
-    pub api_key: String
+    pub base_url: String,
+    pub api_key: String,
+    #[serde(skip, default = "default_lastfm_rate_limit")]
+    pub rate_limit: RateLimitConfig,
+}
+
+impl std::fmt::Debug for LastFmConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LastFmConfig")
+            .field("base_url", &self.base_url)
+            .field("api_key", &REDACTED)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+fn default_lastfm_rate_limit() -> RateLimitConfig {
+    RateLimitConfig { max_rps: 5.0, burst: 5 }
 }
 
 fn build_lastfm() -> Result<LastFmConfig, CrawlerError> {
@@ -460,14 +589,34 @@ fn build_lastfm() -> Result<LastFmConfig, CrawlerError> {
 
     Ok(LastFmConfig {
         base_url: "https://ws.audioscrobbler.com/2.0/".to_string(),
-        api_key
+        api_key,
+        rate_limit: env_rate_limit(
+            "LASTFM_MAX_RPS", "LASTFM_BURST", default_lastfm_rate_limit()
+        ),
     })
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct DiscogsConfig {
-    pub base_url: String, 
-    pub api_key: String
+    pub base_url: String,
+    pub api_key: String,
+    #[serde(skip, default = "default_discogs_rate_limit")]
+    pub rate_limit: RateLimitConfig,
+}
+
+impl std::fmt::Debug for DiscogsConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiscogsConfig")
+            .field("base_url", &self.base_url)
+            .field("api_key", &REDACTED)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+fn default_discogs_rate_limit() -> RateLimitConfig {
+    // Discogs allows ~60 req/min authenticated.
+    RateLimitConfig { max_rps: 1.0, burst: 5 }
 }
 
 fn build_discogs() -> Result<DiscogsConfig, CrawlerError> {
@@ -475,10 +624,53 @@ fn build_discogs() -> Result<DiscogsConfig, CrawlerError> {
 
     Ok(DiscogsConfig {
         base_url: "https://api.discogs.com/".to_string(),
-        api_key
+        api_key,
+        rate_limit: env_rate_limit(
+            "DISCOGS_MAX_RPS", "DISCOGS_BURST", default_discogs_rate_limit()
+        ),
     })
 }
 
+///
+/// Configuration for Invidious, our fallback YouTube cross-reference.
+/// Public instances come and go, so this is a list, tried in order.
+///
+#[derive(Debug, Clone)]
+pub struct InvidiousConfig {
+    pub base_urls: Vec<Url>,
+}
+
+fn build_invidious() -> Result<InvidiousConfig, CrawlerError> {
+    let raw = std::env::var("INVIDIOUS_BASE_URLS")
+        .unwrap_or_else(|_| "https://invidious.f5.si/,https://yewtu.be/".to_string());
+
+    let base_urls: Vec<Url> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let mut url = Url::parse(s).map_err(|e| CrawlerError::Config(
+                format!("INVIDIOUS_BASE_URLS invalid: {e}")
+            ))?;
+            ensure_https(&url).map_err(CrawlerError::Config)?;
+            if !url.path().ends_with('/') {
+                let mut path = url.path().to_string();
+                path.push('/');
+                url.set_path(&path);
+            }
+            Ok(url)
+        })
+        .collect::<Result<Vec<_>, CrawlerError>>()?;
+
+    if base_urls.is_empty() {
+        return Err(CrawlerError::Config(
+            "INVIDIOUS_BASE_URLS resolved to zero instances".to_string()
+        ));
+    }
+
+    Ok(InvidiousConfig { base_urls })
+}
+
 
 ///
 /// AppConfig which holds all requests, etc. needed by fetch module 
@@ -488,11 +680,12 @@ pub struct AppConfig {
     pub identity: IdentityConfig, 
     pub spotify: SpotifyConfig, 
     pub acousticbrainz: AcousticBrainzConfig, 
-    pub lastfm: LastFmConfig, 
+    pub lastfm: LastFmConfig,
     pub discogs: DiscogsConfig,
-    pub musicbrainz: MusicBrainzConfig, 
-    // pub acoustid: AcoustIdConfig, 
-    pub http: HttpConfig, 
+    pub invidious: InvidiousConfig,
+    pub musicbrainz: MusicBrainzConfig,
+    pub acoustid: AcoustIdConfig,
+    pub http: HttpConfig,
     pub persistence: PersistenceConfig, 
     pub matching: MatchingConfig, 
     pub concurrency: ConcurrencyConfig, 
@@ -508,18 +701,191 @@ pub fn load_config() -> Result<AppConfig, CrawlerError> {
     let identity    = build_identity()?; 
     let spotify     = build_spotify()?;
     let acousticbrainz = AcousticBrainzConfig::default(); 
-    let lastfm      = build_lastfm()?; 
-    let discogs     = build_discogs()?; 
+    let lastfm      = build_lastfm()?;
+    let discogs     = build_discogs()?;
+    let invidious   = build_invidious()?;
     let musicbrainz = build_musicbrainz(&identity)?;
-    // let acoustid    = build_acoustid()?;
-    let http        = HttpConfig::default(); 
-    let persistence = PersistenceConfig::default();    
-    let matching    = MatchingConfig::default(); 
-    let concurrency = ConcurrencyConfig::default(); 
-    let logging     = LoggingConfig::default(); 
-
-    Ok( AppConfig { 
-        identity, spotify, acousticbrainz, lastfm, discogs, musicbrainz, 
+    let acoustid    = build_acoustid()?;
+    let http        = HttpConfig::default();
+    let persistence = PersistenceConfig::default();
+    let matching    = MatchingConfig::default();
+    let concurrency = ConcurrencyConfig::default();
+    let logging     = LoggingConfig::default();
+
+    Ok( AppConfig {
+        identity, spotify, acousticbrainz, lastfm, discogs, invidious, musicbrainz, acoustid,
         http, persistence, matching, concurrency, logging
     } )
 }
+
+///
+/// File-based override layer for `load_config_layered`. Every field is
+/// optional so a profile file only needs to set what it changes; anything
+/// left out falls through to the environment, then to the same defaults
+/// `load_config` uses. Field names mirror the env vars each `build_*`
+/// function already reads (lowercased, section-nested).
+///
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ConfigFile {
+    pub identity: IdentityFileConfig,
+    pub spotify: SpotifyFileConfig,
+    pub musicbrainz: MusicBrainzFileConfig,
+    pub lastfm: LastFmFileConfig,
+    pub discogs: DiscogsFileConfig,
+    pub invidious: InvidiousFileConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct IdentityFileConfig {
+    pub application: Option<String>,
+    pub music_brainz_header: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SpotifyFileConfig {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub token_url: Option<String>,
+    pub api_base: Option<String>,
+    pub scopes: Option<String>,
+    pub refresh_skew_secs: Option<u64>,
+    pub max_rps: Option<f32>,
+    pub burst: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MusicBrainzFileConfig {
+    pub base_url: Option<String>,
+    pub inc_recording: Option<String>,
+    pub search_limit: Option<u32>,
+    pub search_offset: Option<u32>,
+    pub max_rps: Option<f32>,
+    pub burst: Option<u32>,
+    pub search_duration_tol: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LastFmFileConfig {
+    pub api_key: Option<String>,
+    pub max_rps: Option<f32>,
+    pub burst: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DiscogsFileConfig {
+    pub api_key: Option<String>,
+    pub max_rps: Option<f32>,
+    pub burst: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct InvidiousFileConfig {
+    pub base_urls: Option<String>,
+}
+
+/// Maps a `ConfigFile` section onto the env vars `build_*` already reads,
+/// without overwriting anything the process environment already set —
+/// this is what gives env > file precedence.
+fn seed_env_from_file(file: &ConfigFile) {
+    let set_if_absent = |key: &str, value: &Option<String>| {
+        if let Some(v) = value {
+            if std::env::var(key).is_err() {
+                std::env::set_var(key, v);
+            }
+        }
+    };
+    let set_num_if_absent = |key: &str, value: Option<impl std::fmt::Display>| {
+        if let Some(v) = value {
+            if std::env::var(key).is_err() {
+                std::env::set_var(key, v.to_string());
+            }
+        }
+    };
+
+    set_if_absent("APPLICATION", &file.identity.application);
+    set_if_absent("MUSIC_BRAINZ_HEADER", &file.identity.music_brainz_header);
+
+    set_if_absent("SPOTIFY_CLIENT_ID", &file.spotify.client_id);
+    set_if_absent("SPOTIFY_CLIENT_SECRET", &file.spotify.client_secret);
+    set_if_absent("SPOTIFY_TOKEN_URL", &file.spotify.token_url);
+    set_if_absent("SPOTIFY_API_BASE", &file.spotify.api_base);
+    set_if_absent("SPOTIFY_SCOPES", &file.spotify.scopes);
+    set_num_if_absent("SPOTIFY_REFRESH_SKEW_SECS", file.spotify.refresh_skew_secs);
+    set_num_if_absent("SPOTIFY_MAX_RPS", file.spotify.max_rps);
+    set_num_if_absent("SPOTIFY_BURST", file.spotify.burst);
+
+    set_if_absent("MB_BASE_URL", &file.musicbrainz.base_url);
+    set_if_absent("MB_INC_RECORDING", &file.musicbrainz.inc_recording);
+    set_num_if_absent("MB_SEARCH_LIMIT", file.musicbrainz.search_limit);
+    set_num_if_absent("MB_SEARCH_OFFSET", file.musicbrainz.search_offset);
+    set_num_if_absent("MB_MAX_RPS", file.musicbrainz.max_rps);
+    set_num_if_absent("MB_BURST", file.musicbrainz.burst);
+    set_num_if_absent("MB_SEARCH_DURATION_TOL", file.musicbrainz.search_duration_tol);
+
+    set_if_absent("LASTFM_API_KEY", &file.lastfm.api_key);
+    set_num_if_absent("LASTFM_MAX_RPS", file.lastfm.max_rps);
+    set_num_if_absent("LASTFM_BURST", file.lastfm.burst);
+
+    set_if_absent("DISCOGS_API_KEY", &file.discogs.api_key);
+    set_num_if_absent("DISCOGS_MAX_RPS", file.discogs.max_rps);
+    set_num_if_absent("DISCOGS_BURST", file.discogs.burst);
+
+    set_if_absent("INVIDIOUS_BASE_URLS", &file.invidious.base_urls);
+}
+
+fn parse_config_file(path: &std::path::Path) -> Result<ConfigFile, CrawlerError> {
+    let text = std::fs::read_to_string(path).map_err(|e|
+        CrawlerError::Config(format!("read config file {}: {e}", path.display()))
+    )?;
+
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&text).map_err(|e|
+            CrawlerError::Config(format!("parse yaml config {}: {e}", path.display()))
+        ),
+        _ => toml::from_str(&text).map_err(|e|
+            CrawlerError::Config(format!("parse toml config {}: {e}", path.display()))
+        ),
+    }
+}
+
+/// Picks `config.{profile}.toml` based on `AppEnv`, driven by `APP_ENV`
+/// (`dev`/`staging`/`prod`, default `dev`) so `load_config_layered(None)`
+/// can select a profile without the caller hardcoding a path.
+fn default_profile_path() -> std::path::PathBuf {
+    let profile = match std::env::var("APP_ENV").as_deref() {
+        Ok("staging") => "staging",
+        Ok("prod") | Ok("production") => "prod",
+        _ => "dev",
+    };
+    std::path::PathBuf::from(format!("config.{profile}.toml"))
+}
+
+/// Layered config load: a TOML/YAML file (explicit `path`, or the
+/// `AppEnv`-selected profile file if it exists) seeds missing env vars,
+/// then `load_config` resolves env over file over the usual defaults.
+/// Precedence: env > file > default.
+pub fn load_config_layered(path: Option<&std::path::Path>) -> Result<AppConfig, CrawlerError> {
+    dotenvy::dotenv().ok();
+
+    let resolved = match path {
+        Some(p) => Some(p.to_path_buf()),
+        None => {
+            let candidate = default_profile_path();
+            candidate.exists().then_some(candidate)
+        }
+    };
+
+    if let Some(p) = resolved {
+        let file = parse_config_file(&p)?;
+        seed_env_from_file(&file);
+    }
+
+    load_config()
+}
@@ -8,18 +8,20 @@
 
 use std::{sync::Arc, time::{Duration, Instant}};
 
-use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{sync::Semaphore, task::JoinHandle, time::sleep};
 use tokio_util::sync::CancellationToken; 
 use tracing::{debug, error, info, warn};
 use uuid::Uuid; 
 
 use crate::{config::{AcousticBrainzConfig, HttpConfig, LoggingConfig}, fetch::LastFmClient};
-use crate::fetch::*;    // all clients are imported 
+use crate::fetch::*;    // all clients are imported
+use crate::ids::{Isrc, Mbid, SpotifyTrackId};
 use crate::persistent::{Job, JobType, Persistent};
 use crate::sink::{DiskZstdSink, RawType};
 use crate::errors::CrawlerError;
-use crate::config::AppConfig; 
+use crate::config::AppConfig;
+use crate::trigram;
+use crate::types::TrackKey;
 
 #[derive(Debug)]
 struct RateGate {
@@ -44,74 +46,61 @@ impl RateGate {
     }
 }
 
-/// Simple function to generate random wait for http_with_retry
-fn generate_backoff(ms: u64, attempt: usize, rng: &mut SmallRng) -> Duration {
-    let exp = (1_u64 << attempt.min(6)) * ms; 
-    let jitter = rng.gen_range(50..=200) as u64; 
-    Duration::from_millis(exp + jitter)
-}
-
-async fn http_with_retry(
-    request: reqwest::RequestBuilder, 
-    max_retries: usize, 
-    backoff_ms: u64
-) -> Result<serde_json::Value, CrawlerError> {
-    let mut rng = SmallRng::from_entropy();
-    let mut attempt = 0_usize; 
-    loop {
-        let response = request.try_clone()
-            .ok_or_else(|| CrawlerError::Http("non-cloneable request".to_string()))?
-            .send()
-            .await;
-        match response {
-            Ok(resp) => {
-                if resp.status().is_success() {
-                    let v = resp.json::<serde_json::Value>().await?; 
-                    return Ok(v);
-                }
-                let status = resp.status(); 
-                let body = resp.text().await.unwrap_or_default();
-                let retryable = status.as_u16() == 429 || status.is_server_error(); 
-                if !retryable || attempt >= max_retries {
-                    return Err(CrawlerError::Http("http.retry".to_string()));
-                }
-                let backoff = generate_backoff(backoff_ms, attempt, &mut rng);
-                warn!(status = %status, backoff = ?backoff.as_millis(), "http.retry");
-                sleep(backoff).await; 
-                attempt += 1;
-            },
-            Err(e) => {
-                if attempt >= max_retries {
-                    return Err(e.into());
-                }
-                let backoff = generate_backoff(backoff_ms, attempt, &mut rng);
-                warn!(backoff = ?backoff.as_millis(), "http.retry.error");
-                sleep(backoff).await; 
-                attempt += 1; 
-            }
-        }
-    }
+/// Builds a `trigram::Candidate` out of a MusicBrainz recording search hit,
+/// skipping any hit that's missing the `id` a candidate needs to be useful.
+fn candidate_from_record(record: &serde_json::Value) -> Option<trigram::Candidate> {
+    let mb_recording_id = record.get("id")?.as_str()?.to_string();
+    let title = record.get("title").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let artist_name = record.get("artist-credit")
+        .and_then(|v| v.as_array())
+        .map(|credits| credits.iter()
+            .filter_map(|c| c.get("name").and_then(|n| n.as_str()))
+            .collect::<Vec<_>>()
+            .join(" "))
+        .unwrap_or_default();
+    let duration_ms = record.get("length").and_then(|v| v.as_u64()).map(|v| v as u32);
+    let isrc = record.get("isrcs")
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    Some(trigram::Candidate {
+        mb_recording_id,
+        mb_release_id: None,
+        title,
+        artist_name,
+        duration_ms,
+        isrc,
+    })
 }
 
 #[derive(Clone, Debug)]
 pub struct CrawlerLimits {
-    pub musicbrainz_limit: usize, 
+    pub musicbrainz_limit: usize,
     pub musicbrainz_ms: u64,
-    pub feature_limit: usize, 
-    pub queue_poll_ms: u64, 
-    pub http_max_retry: usize, 
-    pub http_backoff_ms: u64
+    pub feature_limit: usize,
+    pub queue_poll_ms: u64,
+    /// Base delay passed to `Persistent::retry_job`'s exponential backoff.
+    pub retry_base_delay_secs: i64,
+    /// Lease window passed to `Persistent::reap_stale_jobs`, and the period
+    /// the reaper task sleeps between sweeps.
+    pub job_lease_secs: i64,
+    /// Minimum `trigram::confidence` a text-search candidate must clear
+    /// before `lookup_mbid_by_query` will hand it back for `set_mbid`.
+    pub link_confidence_threshold: f32,
 }
 
 impl Default for CrawlerLimits {
     fn default() -> Self {
         Self {
-            musicbrainz_limit: 1, 
+            musicbrainz_limit: 1,
             musicbrainz_ms: 1100,
-            feature_limit: 4, 
-            queue_poll_ms: 300, 
-            http_max_retry: 3, 
-            http_backoff_ms: 500 
+            feature_limit: 4,
+            queue_poll_ms: 300,
+            retry_base_delay_secs: 30,
+            job_lease_secs: 300,
+            link_confidence_threshold: 0.72,
         }
     }
 }
@@ -119,9 +108,28 @@ impl Default for CrawlerLimits {
 #[derive(Clone)]
 pub struct Clients {
     pub spotify: Arc<SpotifyClient>,
-    pub musicbrainz: Arc<MusicBrainzClient>, 
-    pub acousticbrainz: Arc<AcousticBrainzClient>, 
-    pub lastfm: Arc<LastFmClient> 
+    pub musicbrainz: Arc<MusicBrainzClient>,
+    pub acousticbrainz: Arc<AcousticBrainzClient>,
+    pub lastfm: Arc<LastFmClient>,
+    pub acoustid: Arc<AcoustIdClient>
+}
+
+impl Clients {
+    pub fn new(
+        spotify: SpotifyClient,
+        musicbrainz: MusicBrainzClient,
+        acousticbrainz: AcousticBrainzClient,
+        lastfm: LastFmClient,
+        acoustid: AcoustIdClient
+    ) -> Self {
+        Self {
+            spotify: Arc::new(spotify),
+            musicbrainz: Arc::new(musicbrainz),
+            acousticbrainz: Arc::new(acousticbrainz),
+            lastfm: Arc::new(lastfm),
+            acoustid: Arc::new(acoustid)
+        }
+    }
 }
 
 pub struct Crawler {
@@ -181,8 +189,9 @@ impl Crawler {
             "crawler.start",
         );
 
-        let link_handle = self.spawn_link_workers(); 
-        let feat_handle = self.spawn_feature_workers(); 
+        let link_handle = self.spawn_link_workers();
+        let feat_handle = self.spawn_feature_workers();
+        self.spawn_stale_reaper();
 
         let shutdown = self.shutdown.clone();
         let trigger = tokio::spawn(async move {
@@ -220,10 +229,31 @@ impl Crawler {
     }
 
     fn spawn_feature_workers(&self) -> JoinHandle<()> {
-        let this = self.clone_for_task(); 
+        let this = self.clone_for_task();
         tokio::spawn(async move { this.features_loop().await })
     }
 
+    /// Spawns the stale-job reaper; like the worker loops it reads
+    /// `self.shutdown`, but a failure here shouldn't tear down a crawl - a
+    /// stuck job just stays stuck a bit longer.
+    fn spawn_stale_reaper(&self) -> JoinHandle<()> {
+        let this = self.clone_for_task();
+        tokio::spawn(async move { this.reap_loop().await })
+    }
+
+    /// Periodically puts `active` jobs whose claim lease has lapsed back to
+    /// `pending`, so a worker that crashed or hung mid-job doesn't strand it
+    /// until the next restart.
+    async fn reap_loop(&self) {
+        let period = Duration::from_secs(self.limits.job_lease_secs.max(1) as u64);
+        while !self.shutdown.is_cancelled() {
+            sleep(period).await;
+            if let Err(e) = self.db.reap_stale_jobs(self.limits.job_lease_secs).await {
+                error!(error = ?e, "reap_stale_jobs failed");
+            }
+        }
+    }
+
     fn clone_for_task(&self) -> Self {
         Self {
             http: self.http.clone(), 
@@ -262,8 +292,14 @@ impl Crawler {
                 Err(_) => break 
             }; 
 
+            let job_id = job.job_id;
             if let Err(e) = self.process_link_job(job).await {
-                error!(error = ?e, "link job failed");
+                error!(error = ?e, job_id, "link job failed");
+                if let Err(re) = self.db.retry_job(
+                    job_id, &e.to_string(), self.limits.retry_base_delay_secs
+                ).await {
+                    error!(error = ?re, job_id, "retry_job failed");
+                }
             }
         }
         info!("crawler.link.loop.stop");
@@ -274,13 +310,15 @@ impl Crawler {
             job_id = job.job_id, track = %job.track_id, 
             attempt = job.attempt, "link.process");
 
-        let meta = self.db.get_track_metadata(&job.track_id).await 
-            .map_err(CrawlerError::Db("link failure".to_string()))?; 
+        let meta = self.db.get_track_metadata(&job.track_id).await
+            .map_err(|e| CrawlerError::Db(format!("link failure: {e}")))?
+            .ok_or_else(|| CrawlerError::NotFound(format!("track {} not found", job.track_id)))?;
 
-        let mbid = if let Some(isrc) = meta.isrc.as_deref() {
-            self.lookup_mbid_by_isrc(isrc).await? 
+        let mbid = if let Some(isrc) = meta.isrc.as_ref() {
+            self.lookup_mbid_by_isrc(isrc.as_str()).await?
         } else {
-            self.lookup_mbid_by_query(&meta.title, &meta.first_artist()).await?
+            let title = meta.title.as_deref().unwrap_or("");
+            self.lookup_mbid_by_query(title, meta.first_artist()).await?
         };
 
         self.db.set_mbid(&job.track_id, &mbid).await?; 
@@ -294,11 +332,10 @@ impl Crawler {
     }
 
     async fn lookup_mbid_by_isrc(&self, isrc: &str) -> Result<String, CrawlerError> {
-        let resp = self.clients.musicbrainz.lookup_isrc(isrc);
-        let value = http_with_retry(
-            resp, self.limits.http_max_retry,
-            self.limits.http_backoff_ms
-        ).await?;
+        let isrc = Isrc::parse(isrc)?;
+        let resp = self.clients.musicbrainz.lookup_isrc(&isrc);
+        let value = self.clients.musicbrainz.send(resp, true).await?
+            .json::<serde_json::Value>().await?;
         let records = value["recordings"].as_array().unwrap();
         let mbid = records.iter() 
             .filter_map(|r| r.get("id").and_then(|x| x.as_str()))
@@ -307,20 +344,41 @@ impl Crawler {
         Ok(mbid.to_string())
     }
 
-    async fn lookup_mbid_by_query(&self, title: &str, artist: &str) -> 
+    /// Unlike `lookup_mbid_by_isrc`, a text search can return several
+    /// plausible recordings, so candidates are scored with
+    /// `trigram::confidence` and only handed back if the best one clears
+    /// `CrawlerLimits::link_confidence_threshold` - a weak match is left
+    /// unlinked rather than guessed at.
+    async fn lookup_mbid_by_query(&self, title: &str, artist: &str) ->
         Result<String, CrawlerError> {
         let query = format!("recording:\"{}\" AND artist:\"{}\"", title, artist);
         let resp = self.clients.musicbrainz.search_recording(&query, 10, 0);
-        let value = http_with_retry(
-            resp, self.limits.http_max_retry,
-            self.limits.http_backoff_ms
-        ).await?;
-        let records = value["recordings"].as_array().unwrap();
-        let mbid = records.iter() 
-            .filter_map(|r| r.get("id").and_then(|x| x.as_str()))
-            .next()
-            .ok_or_else(|| CrawlerError::Http("no recording for ISRC".to_string()))?;
-        Ok(mbid.to_string())
+        let value = self.clients.musicbrainz.send(resp, true).await?
+            .json::<serde_json::Value>().await?;
+
+        let candidates: Vec<trigram::Candidate> = value["recordings"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(candidate_from_record)
+            .collect();
+
+        let key = TrackKey {
+            spotify_id: None,
+            isrc: None,
+            title: Some(title.to_string()),
+            artist_name: Some(artist.to_string()),
+            duration_ms: None,
+        };
+
+        trigram::best_candidate(
+            Uuid::nil(), &key, &candidates, self.limits.link_confidence_threshold
+        )
+            .and_then(|link| link.mb_recording_id)
+            .map(|id| id.0)
+            .ok_or_else(|| CrawlerError::NotFound(
+                "no recording cleared the confidence threshold".to_string()
+            ))
     }
 
     async fn features_loop(&self) {
@@ -342,8 +400,14 @@ impl Crawler {
                 Ok(p) => p, 
                 Err(_) => break 
             };
+            let job_id = job.job_id;
             if let Err(e) = self.process_features_job(job).await {
-                error!(error = ?e, "features job failed");
+                error!(error = ?e, job_id, "features job failed");
+                if let Err(re) = self.db.retry_job(
+                    job_id, &e.to_string(), self.limits.retry_base_delay_secs
+                ).await {
+                    error!(error = ?re, job_id, "retry_job failed");
+                }
             }
         }
         info!("crawler.features.loop.stop");
@@ -353,26 +417,26 @@ impl Crawler {
         debug!(job_id = job.job_id, track = %job.track_id, attempt = job.attempt, 
             "features.process");
 
-        let meta = self.db.get_track_metadata(&job.track_id).await 
-            .map_err(CrawlerError::Db("no metadata for id".to_string()))?; 
-        let mbid = meta.mbid.as_deref().ok_or_else(
-            CrawlerError::NotFound("No mbid found".to_string()
-        ))?;
-
-        let highlevel = self.clients.acousticbrainz.features(mbid, "high-level");
-        let highlevel = http_with_retry(
-            highlevel, 
-            self.limits.http_max_retry, 
-            self.limits.http_backoff_ms
-        );
+        let meta = self.db.get_track_metadata(&job.track_id).await
+            .map_err(|e| CrawlerError::Db(format!("no metadata for id: {e}")))?
+            .ok_or_else(|| CrawlerError::NotFound(format!("track {} not found", job.track_id)))?;
+        let mbid = meta.mb_recording_id.clone().ok_or_else(
+            || CrawlerError::NotFound("No mbid found".to_string())
+        )?;
 
-        let path_highlevel = self.sink.write_json(RawType::ABHighLevel, mbid, &highlevel);
+        let highlevel = self.clients.acousticbrainz.features(&mbid, "high-level");
+        let highlevel = self.clients.acousticbrainz.send(highlevel, true).await?
+            .json::<serde_json::Value>().await?;
+
+        let path_highlevel = self.sink.write_json(
+            RawType::ABHighLevel, mbid.as_str(), highlevel.clone()
+        )?;
         self.db.index_raw_file(
-            &job.track_id, 
-            "acousticbrainz", 
+            &job.track_id,
+            "acousticbrainz",
             "high-level",
-            mbid, 
-            path_highlevel
+            mbid.as_str(),
+            &path_highlevel.to_string_lossy()
         ).await?;
 
         let (highlevel_numeric, highlevel_text) = DiskZstdSink::extract_high_level(
@@ -384,20 +448,19 @@ impl Crawler {
         self.db.upsert_features_text(job.track_id, "acousticbrainz", &highlevel_text)
             .await?; 
 
-        let lowlevel = self.clients.acousticbrainz.features(mbid, "low-level");
-        let lowlevel = http_with_retry(
-            lowlevel, 
-            self.limits.http_max_retry, 
-            self.limits.http_backoff_ms
-        );
+        let lowlevel = self.clients.acousticbrainz.features(&mbid, "low-level");
+        let lowlevel = self.clients.acousticbrainz.send(lowlevel, true).await?
+            .json::<serde_json::Value>().await?;
 
-        let path_lowlevel = self.sink.write_json(RawType::ABLowLevel, mbid, &lowlevel);
+        let path_lowlevel = self.sink.write_json(
+            RawType::ABLowLevel, mbid.as_str(), lowlevel.clone()
+        )?;
         self.db.index_raw_file(
-            &job.track_id, 
-            "acousticbrainz", 
+            &job.track_id,
+            "acousticbrainz",
             "low-level",
-            mbid, 
-            path_lowlevel
+            mbid.as_str(),
+            &path_lowlevel.to_string_lossy()
         ).await?;
 
         let lowlevel_numeric = DiskZstdSink::extract_low_level(&lowlevel); 
@@ -406,30 +469,31 @@ impl Crawler {
             .await?; 
 
         // Get tags from mbid, if fails get conventionally else warning 
-        let mut tags = {
+        let mut tags: Result<serde_json::Value, CrawlerError> = async {
             let resp = self.clients.lastfm.track_top_tags_by_mbid(mbid);
-            http_with_retry(resp, self.limits.http_max_retry, self.limits.http_backoff_ms)
-                .await 
-        };
-        
+            self.clients.lastfm.send(resp, true).await?
+                .json::<serde_json::Value>().await
+                .map_err(CrawlerError::from)
+        }.await;
+
         if tags.is_err() {
-            let artist = meta.first_artist(); 
+            let artist = meta.first_artist();
             let resp = self.clients.lastfm.track_top_tags(&artist, meta.title);
-            tags = http_with_retry(
-                resp,
-                self.limits.http_max_retry, 
-                self.limits.http_backoff_ms
-            ).await;
+            tags = async {
+                self.clients.lastfm.send(resp, true).await?
+                    .json::<serde_json::Value>().await
+                    .map_err(CrawlerError::from)
+            }.await;
         }
 
         if let Ok(tags) = tags {
-            let key = meta.mbid.as_deref().unwrap_or_else(|| { 
-                meta.spotify_id.as_deref().unwrap_or("unknown");       
-            });
-            let path_tags = self.sink.write_json(RawType::LastFmTopTags, key, &tags)
-                .await?;             
-            self.db.index_raw_file(job.track_id, "lastfm", "toptags", key, path_tags)
-                .await?; 
+            let key = meta.mb_recording_id.as_ref().map(Mbid::as_str)
+                .or_else(|| meta.spotify_id.as_ref().map(SpotifyTrackId::as_str))
+                .unwrap_or("unknown");
+            let path_tags = self.sink.write_json(RawType::LastFmTopTags, key, tags)?;
+            self.db.index_raw_file(
+                &job.track_id, "lastfm", "toptags", key, &path_tags.to_string_lossy()
+            ).await?;
         } else {
             warn!(track = %job.track_id, "lastfm tags missing");
         }
@@ -22,6 +22,10 @@ pub enum CrawlerError {
     NotFound(String),
     #[error("db error: {0}")]
     Db(String),
+    #[error("invalid id: {0}")]
+    InvalidId(String),
+    #[error("invalid job {job_id}: {detail}")]
+    InvalidJob { job_id: i64, detail: String },
     #[error("io error: {0}")]
     Io(#[from] std::io::Error)
 }
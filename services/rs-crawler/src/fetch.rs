@@ -5,13 +5,20 @@
 //! returning unparsed data, handling retries, etc. 
 //!
 
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Deserialize;
+use tokio::sync::RwLock;
 use url::Url;
 use reqwest::{Client, header, redirect, RequestBuilder};
 use crate::config::{
-    HttpConfig, IdentityConfig, MusicBrainzConfig, SpotifyConfig,
-    AcousticBrainzConfig, LastFmConfig, DiscogsConfig
-}; 
-use crate::CrawlerError; 
+    AcoustIdConfig, HttpConfig, IdentityConfig, InvidiousConfig, MusicBrainzConfig, RetryConfig,
+    SpotifyConfig, AcousticBrainzConfig, LastFmConfig, DiscogsConfig
+};
+use crate::ids::{Isrc, Mbid, SpotifyTrackId};
+use crate::ratelimit::{send_with_retry, RateLimiter};
+use crate::CrawlerError;
 
 /// Client building functionality 
 fn client_helper(http: &HttpConfig) -> reqwest::ClientBuilder  {
@@ -67,30 +74,44 @@ pub fn musicbrainz_client(http: &HttpConfig, id: &IdentityConfig) ->
 
 #[derive(Clone, Debug)]
 pub struct SpotifyClient {
-    pub http: Client, 
-    pub cfg: SpotifyConfig
+    pub http: Client,
+    pub cfg: SpotifyConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
 }
 
 impl SpotifyClient {
-    pub fn new(http_config: &HttpConfig, cfg: &SpotifyConfig) -> 
+    pub fn new(http_config: &HttpConfig, cfg: &SpotifyConfig) ->
         Result<Self, CrawlerError> {
 
-        let http = base_client(http_config)?; 
-        Ok( Self { 
-            http, 
-            cfg: cfg.clone()
+        let http = base_client(http_config)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_config.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(
+                cfg.rate_limit.max_rps, cfg.rate_limit.burst
+            )),
         })
     }
 
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    /// `idempotent` should be `true` for GETs and the token-credentials
+    /// POST, `false` for anything with side effects.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<reqwest::Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), idempotent).await
+    }
+
     pub fn token_request(&self) -> reqwest::RequestBuilder {
-        self.http 
+        self.http
             .post(self.cfg.token_url.clone())
             .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
             .body("grant_type=client_credentials")
     }
 
     /// GET /v1/tracks/{id}
-    pub fn track(&self, track_id: &str, bearer: &str) -> reqwest::RequestBuilder {
+    pub fn track(&self, track_id: &SpotifyTrackId, bearer: &str) -> reqwest::RequestBuilder {
         let url = self.cfg.api_base.join(&format!("tracks/{track_id}")).unwrap();
         self.http.get(url).bearer_auth(bearer)
     }
@@ -114,34 +135,117 @@ impl SpotifyClient {
     }
 }
 
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[allow(dead_code)]
+    token_type: String,
+    expires_in: u64,
+}
+
+struct CachedToken {
+    bearer: String,
+    expires_at: Instant,
+}
+
+/// Performs the Spotify client-credentials grant and caches the resulting
+/// bearer token behind an `Arc<RwLock<..>>` so concurrent callers share one
+/// valid token instead of each re-minting their own. Refreshes proactively
+/// once the cached token is within `SpotifyConfig::refresh_skew` of expiry.
+#[derive(Clone)]
+pub struct SpotifyTokenManager {
+    client: SpotifyClient,
+    cached: Arc<RwLock<Option<CachedToken>>>,
+}
+
+impl SpotifyTokenManager {
+    pub fn new(client: SpotifyClient) -> Self {
+        Self { client, cached: Arc::new(RwLock::new(None)) }
+    }
+
+    /// Returns a live `Authorization` header value, refreshing the cached
+    /// token if it's missing or within its safety margin of expiring.
+    pub async fn get_token(&self) -> Result<String, CrawlerError> {
+        if let Some(bearer) = self.cached_bearer().await {
+            return Ok(format!("Bearer {bearer}"));
+        }
+        self.refresh().await
+    }
+
+    async fn cached_bearer(&self) -> Option<String> {
+        let guard = self.cached.read().await;
+        guard.as_ref()
+            .filter(|tok| tok.expires_at > Instant::now())
+            .map(|tok| tok.bearer.clone())
+    }
+
+    async fn refresh(&self) -> Result<String, CrawlerError> {
+        let mut guard = self.cached.write().await;
+        // Another task may have refreshed while we waited on the lock.
+        if let Some(tok) = guard.as_ref() {
+            if tok.expires_at > Instant::now() {
+                return Ok(format!("Bearer {}", tok.bearer));
+            }
+        }
+
+        let resp = self.client.send(
+            self.client.token_request()
+                .basic_auth(&self.client.cfg.client_id, Some(&self.client.cfg.client_secret)),
+            true,
+        ).await?;
+        let parsed: TokenResponse = resp.json().await?;
+
+        let ttl = std::time::Duration::from_secs(parsed.expires_in)
+            .saturating_sub(self.client.cfg.refresh_skew);
+        *guard = Some(CachedToken {
+            bearer: parsed.access_token.clone(),
+            expires_at: Instant::now() + ttl,
+        });
+
+        Ok(format!("Bearer {}", parsed.access_token))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MusicBrainzClient {
-    pub http: Client, 
-    pub base: Url, 
-    pub inc_recording: String 
+    pub http: Client,
+    pub base: Url,
+    pub inc_recording: String,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
 }
 
 impl MusicBrainzClient {
     pub fn new(
-        http_config: &HttpConfig, 
-        id: &IdentityConfig, 
+        http_config: &HttpConfig,
+        id: &IdentityConfig,
         cfg: &MusicBrainzConfig) -> Result<Self, CrawlerError> {
-        let http = musicbrainz_client(http_config, id)?; 
-        Ok( Self{ 
-            http, 
+        let http = musicbrainz_client(http_config, id)?;
+        Ok( Self{
+            http,
             base: cfg.base_url.clone(),
-            inc_recording: cfg.inc_recording.clone()
+            inc_recording: cfg.inc_recording.clone(),
+            retry: http_config.retry.clone(),
+            // MusicBrainz asks integrators to stay near 1 req/s per IP;
+            // respect whatever MB_MAX_RPS/MB_BURST are configured to.
+            limiter: Arc::new(RateLimiter::new(cfg.rate_limit.max_rps, cfg.rate_limit.burst)),
         })
     }
 
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<reqwest::Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), idempotent).await
+    }
+
     /// GET /ws/v2/isrc/{ISRC}?fmt=json
-    pub fn lookup_isrc(&self, isrc: &str) -> RequestBuilder {
+    pub fn lookup_isrc(&self, isrc: &Isrc) -> RequestBuilder {
         let url = self.base.join(&format!("isrc/{isrc}?fmt=json")).unwrap();
         self.http.get(url)
     }
 
     /// GET /ws/2/recording/{MBID}?fmt=json&inc=artist-credits+isrcs+releases
-    pub fn lookup_recording(&self, mbid: &str) -> RequestBuilder {
+    pub fn lookup_recording(&self, mbid: &Mbid) -> RequestBuilder {
         let mut url = self.base.join(&format!("recording/{mbid}")).unwrap();
         url.set_query(Some(&format!("fmt=json&inc={}", self.inc_recording)));
         self.http.get(url)
@@ -160,7 +264,7 @@ impl MusicBrainzClient {
     }
 
     /// GET /ws/2/release/{MBID}?fmt=json&inc=...
-    pub fn lookup_release(&self, mbid: &str, inc: &str) -> RequestBuilder {
+    pub fn lookup_release(&self, mbid: &Mbid, inc: &str) -> RequestBuilder {
         let mut url = self.base.join(&format!("release/{mbid}")).unwrap();
         url.set_query(Some(&format!("fmt=json&inc={inc}")));
         self.http.get(url)
@@ -169,17 +273,19 @@ impl MusicBrainzClient {
 
 #[derive(Clone, Debug)]
 pub struct AcousticBrainzClient {
-    pub http: Client, 
-    pub base: Url 
+    pub http: Client,
+    pub base: Url,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
 }
 
 impl AcousticBrainzClient {
     pub fn new(
-        http_config: &HttpConfig, 
-        identity: &IdentityConfig, 
+        http_config: &HttpConfig,
+        identity: &IdentityConfig,
         acousticbrainz: &AcousticBrainzConfig
     ) -> Result<Self, CrawlerError> {
-        let mut headers = header::HeaderMap::new(); 
+        let mut headers = header::HeaderMap::new();
         headers.insert(header::ACCEPT, header::HeaderValue::from_static(
             "application/json"
         ));
@@ -193,12 +299,25 @@ impl AcousticBrainzClient {
         let http = client_with_headers(http_config, headers)?;
 
         let base = acousticbrainz.base_url.clone();
-        Ok( Self{ http, base })
+        Ok( Self{
+            http,
+            base,
+            retry: http_config.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(
+                acousticbrainz.rate_limit.max_rps, acousticbrainz.rate_limit.burst
+            )),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<reqwest::Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), idempotent).await
     }
 
     /// GET {base}/api/v1/{mbid}/{level}
-    /// Ensure level is either high_level or low_level (TODO?) 
-    pub fn features(&self, mb_recording_id: &str, level: &str) -> RequestBuilder {
+    /// Ensure level is either high_level or low_level (TODO?)
+    pub fn features(&self, mb_recording_id: &Mbid, level: &str) -> RequestBuilder {
         let url = self.base.join(
             &format!("api/v1/{mb_recording_id}/{level}")
         ).unwrap();
@@ -208,20 +327,35 @@ impl AcousticBrainzClient {
 
 #[derive(Clone, Debug)]
 pub struct LastFmClient {
-    pub http: Client, 
+    pub http: Client,
     pub cfg: LastFmConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
 }
 
 impl LastFmClient {
-    pub fn new(http_cfg: &HttpConfig, last_cfg: &LastFmConfig) -> 
+    pub fn new(http_cfg: &HttpConfig, last_cfg: &LastFmConfig) ->
         Result<Self, CrawlerError> {
-        let mut headers = header::HeaderMap::new(); 
+        let mut headers = header::HeaderMap::new();
         headers.insert(
-            header::ACCEPT, 
+            header::ACCEPT,
             header::HeaderValue::from_static("application/json")
         );
-        let http = client_with_headers(http_cfg, headers)?; 
-        Ok( Self{ http, cfg: last_cfg.clone() })
+        let http = client_with_headers(http_cfg, headers)?;
+        Ok( Self{
+            http,
+            cfg: last_cfg.clone(),
+            retry: http_cfg.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(
+                last_cfg.rate_limit.max_rps, last_cfg.rate_limit.burst
+            )),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<reqwest::Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), idempotent).await
     }
 
     /// GET /?method=track.getTopTags&artist=...&track=...&api_key=...&format=json
@@ -271,14 +405,16 @@ impl LastFmClient {
 
 #[derive(Clone, Debug)]
 pub struct DiscogsClient {
-    pub http: Client, 
-    pub cfg: DiscogsConfig
+    pub http: Client,
+    pub cfg: DiscogsConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
 }
 
 impl DiscogsClient {
-    pub fn new(http_cfg: &HttpConfig, identity: &IdentityConfig, dg_cfg: &DiscogsConfig) 
+    pub fn new(http_cfg: &HttpConfig, identity: &IdentityConfig, dg_cfg: &DiscogsConfig)
         -> Result<Self, CrawlerError> {
-        let mut headers = header::HeaderMap::new(); 
+        let mut headers = header::HeaderMap::new();
         headers.insert(
             header::USER_AGENT,
             header::HeaderValue::from_str(&identity.mb_user_agent)
@@ -286,7 +422,20 @@ impl DiscogsClient {
         );
         let http = client_with_headers(http_cfg, headers)?;
 
-        Ok(Self { http, cfg: dg_cfg.clone() })
+        Ok(Self {
+            http,
+            cfg: dg_cfg.clone(),
+            retry: http_cfg.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(
+                dg_cfg.rate_limit.max_rps, dg_cfg.rate_limit.burst
+            )),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<reqwest::Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), idempotent).await
     }
 
     /// GET /database/search?artist=...&track=...&type=release&per_page=&page=
@@ -316,3 +465,118 @@ impl DiscogsClient {
         rb.header(header::AUTHORIZATION, format!("Discogs token={}", self.cfg.api_key))
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct InvidiousClient {
+    pub http: Client,
+    pub cfg: InvidiousConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
+}
+
+impl InvidiousClient {
+    pub fn new(http_config: &HttpConfig, cfg: &InvidiousConfig) -> Result<Self, CrawlerError> {
+        let http = base_client(http_config)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_config.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(
+                http_config.default_max_rps, http_config.default_burst
+            )),
+        })
+    }
+
+    /// GET {instance}/api/v1/search?q=...&sort_by=...&type=video, falling
+    /// through to the next configured instance on connection failure since
+    /// public Invidious hosts come and go.
+    pub async fn search(&self, query: &str, sort_by: &str) -> Result<serde_json::Value, CrawlerError> {
+        let mut last_err = None;
+
+        for base in &self.cfg.base_urls {
+            let url = base.join("api/v1/search").unwrap();
+            let rb = self.http.get(url).query(&[
+                ("q", query),
+                ("sort_by", sort_by),
+                ("type", "video"),
+            ]);
+
+            match send_with_retry(rb, &self.retry, Some(&self.limiter), true).await {
+                Ok(resp) if resp.status().is_success() => {
+                    return resp.json::<serde_json::Value>().await.map_err(CrawlerError::from);
+                }
+                Ok(resp) => last_err = Some(CrawlerError::Http(format!("status {}", resp.status()))),
+                Err(e) => last_err = Some(e),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(||
+            CrawlerError::Config("no invidious instances configured".to_string())
+        ))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AcoustIdClient {
+    pub http: Client,
+    pub cfg: AcoustIdConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
+}
+
+impl AcoustIdClient {
+    pub fn new(http_cfg: &HttpConfig, cfg: &AcoustIdConfig) -> Result<Self, CrawlerError> {
+        let http = base_client(http_cfg)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_cfg.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(
+                cfg.rate_limit.max_rps, cfg.rate_limit.burst
+            )),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<reqwest::Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), idempotent).await
+    }
+
+    /// GET lookup?client=...&meta=...&duration=...&fingerprint=...
+    pub fn lookup_request(&self, fingerprint: &str, duration_secs: u32) -> RequestBuilder {
+        let url = self.cfg.base_url.join("lookup").unwrap();
+        self.http.get(url).query(&[
+            ("client", self.cfg.api_key.as_str()),
+            ("meta", self.cfg.meta.as_str()),
+            ("duration", &duration_secs.to_string()),
+            ("fingerprint", fingerprint),
+        ])
+    }
+
+    /// Submits a Chromaprint `fingerprint` + `duration_secs` and returns the
+    /// matched recording MBIDs ordered by AcoustId's own match score (best
+    /// first).
+    pub async fn recording_mbids(&self, fingerprint: &str, duration_secs: u32) ->
+        Result<Vec<String>, CrawlerError> {
+        let resp = self.send(self.lookup_request(fingerprint, duration_secs), true).await?;
+        let body: serde_json::Value = resp.json().await.map_err(CrawlerError::from)?;
+
+        let mut scored: Vec<(f64, String)> = body["results"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .flat_map(|result| {
+                let score = result["score"].as_f64().unwrap_or(0.0);
+                result["recordings"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(move |rec| rec["id"].as_str().map(|id| (score, id.to_string())))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(scored.into_iter().map(|(_, id)| id).collect())
+    }
+}
@@ -0,0 +1,175 @@
+//!
+//! src/ids.rs  Andrew Belles  Sept 14th, 2025
+//!
+//! Validated, zero-copy newtypes for the identifiers we pass across
+//! Spotify/MusicBrainz/Discogs/ISRC boundaries. Each type wraps a
+//! `Cow<'a, str>` so a caller holding a borrowed `&str` (e.g. straight out
+//! of a parsed JSON response) doesn't have to allocate just to satisfy the
+//! type, while call sites that need to own the value still can.
+//!
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::CrawlerError;
+
+fn invalid(kind: &str, value: &str) -> CrawlerError {
+    CrawlerError::InvalidId(format!("{kind}: {value}"))
+}
+
+/// Spotify base-62 track/album/artist id: exactly 22 alphanumeric chars.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SpotifyTrackId<'a>(Cow<'a, str>);
+
+impl<'a> SpotifyTrackId<'a> {
+    pub fn parse(value: &'a str) -> Result<Self, CrawlerError> {
+        let ok = value.len() == 22 && value.bytes().all(|b| b.is_ascii_alphanumeric());
+        if !ok {
+            return Err(invalid("spotify id", value));
+        }
+        Ok(Self(Cow::Borrowed(value)))
+    }
+
+    pub fn into_owned(self) -> SpotifyTrackId<'static> {
+        SpotifyTrackId(Cow::Owned(self.0.into_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> TryFrom<&'a str> for SpotifyTrackId<'a> {
+    type Error = CrawlerError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for SpotifyTrackId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Discogs numeric release id, e.g. the trailing integer in `/releases/{id}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DiscogsReleaseId<'a>(Cow<'a, str>);
+
+impl<'a> DiscogsReleaseId<'a> {
+    pub fn parse(value: &'a str) -> Result<Self, CrawlerError> {
+        let ok = !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+        if !ok {
+            return Err(invalid("discogs release id", value));
+        }
+        Ok(Self(Cow::Borrowed(value)))
+    }
+
+    pub fn into_owned(self) -> DiscogsReleaseId<'static> {
+        DiscogsReleaseId(Cow::Owned(self.0.into_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> TryFrom<&'a str> for DiscogsReleaseId<'a> {
+    type Error = CrawlerError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for DiscogsReleaseId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// MusicBrainz identifier: a canonical UUID (8-4-4-4-12 hex, lowercase or not).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Mbid<'a>(Cow<'a, str>);
+
+impl<'a> Mbid<'a> {
+    pub fn parse(value: &'a str) -> Result<Self, CrawlerError> {
+        if !is_uuid_shape(value) {
+            return Err(invalid("mbid", value));
+        }
+        Ok(Self(Cow::Borrowed(value)))
+    }
+
+    pub fn into_owned(self) -> Mbid<'static> {
+        Mbid(Cow::Owned(self.0.into_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Mbid<'a> {
+    type Error = CrawlerError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Mbid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+fn is_uuid_shape(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    let lens = [8, 4, 4, 4, 12];
+    groups.len() == lens.len()
+        && groups
+            .iter()
+            .zip(lens)
+            .all(|(g, len)| g.len() == len && g.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// International Standard Recording Code: `CCXXXYYNNNNN`
+/// (2-letter country, 3-char registrant, 2-digit year, 5-digit designation).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Isrc<'a>(Cow<'a, str>);
+
+impl<'a> Isrc<'a> {
+    pub fn parse(value: &'a str) -> Result<Self, CrawlerError> {
+        let bytes = value.as_bytes();
+        let ok = bytes.len() == 12
+            && bytes[0..2].iter().all(|b| b.is_ascii_alphabetic())
+            && bytes[2..5].iter().all(|b| b.is_ascii_alphanumeric())
+            && bytes[5..7].iter().all(|b| b.is_ascii_digit())
+            && bytes[7..12].iter().all(|b| b.is_ascii_digit());
+        if !ok {
+            return Err(invalid("isrc", value));
+        }
+        Ok(Self(Cow::Borrowed(value)))
+    }
+
+    pub fn into_owned(self) -> Isrc<'static> {
+        Isrc(Cow::Owned(self.0.into_owned()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> TryFrom<&'a str> for Isrc<'a> {
+    type Error = CrawlerError;
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Isrc<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
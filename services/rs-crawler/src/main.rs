@@ -7,13 +7,19 @@
 //!
 
 
-mod config; 
-mod errors; 
-mod logging; 
+mod config;
+mod errors;
+mod logging;
 
+mod crawler;
 mod fetch;
-mod persistent; 
+mod ids;
+mod persistent;
+mod provider;
+mod ratelimit;
 mod sink;
+mod trigram;
+mod types;
 
 use crate::errors::CrawlerError;
 
@@ -23,20 +29,32 @@ async fn main() -> Result<(), CrawlerError> {
     let _    = logging::init_logging(&cfgs.logging)?;
 
     println!("Configuration: {:#?}", cfgs);
-    
+
     tracing::info!(
-        service="rs-id-linker", 
-        version=%env!("CARGO_PKG_VERSION"), 
+        service="rs-crawler",
+        version=%env!("CARGO_PKG_VERSION"),
         "starting"
     );
 
-    let spotify     = fetch::SpotifyClient::new(&cfgs.http, &cfgs.spotify)?;
-    let musicbrainz = fetch::MusicBrainzClient::new(
-        &cfgs.http, 
-        &cfgs.identity, 
+    let db = persistent::Persistent::init(&cfgs.persistence.db_url).await?;
+
+    let spotify        = fetch::SpotifyClient::new(&cfgs.http, &cfgs.spotify)?;
+    let musicbrainz    = fetch::MusicBrainzClient::new(
+        &cfgs.http,
+        &cfgs.identity,
         &cfgs.musicbrainz
     )?;
-    let acoust     = fetch::AcoustIdClient::new(&cfgs.http, &cfgs.acoustid)?;
+    let acousticbrainz = fetch::AcousticBrainzClient::new(
+        &cfgs.http, &cfgs.identity, &cfgs.acousticbrainz
+    )?;
+    let lastfm         = fetch::LastFmClient::new(&cfgs.http, &cfgs.lastfm)?;
+    let acoustid       = fetch::AcoustIdClient::new(&cfgs.http, &cfgs.acoustid)?;
+    let clients = crawler::Clients::new(spotify, musicbrainz, acousticbrainz, lastfm, acoustid);
+
+    let sink   = sink::DiskZstdSink::new(&cfgs.persistence.raw_store_root, 3);
+    let limits = crawler::CrawlerLimits::default();
+
+    crawler::Crawler::new(&cfgs, db, clients, sink, limits).run().await?;
 
     Ok(())
 }
@@ -67,10 +85,11 @@ mod tests {
         let cfgs = config::load_config()?;
         let spotify = fetch::SpotifyClient::new(&cfgs.http, &cfgs.spotify)?;
 
-        let token_response = spotify.token_request()
-            .basic_auth(&cfgs.spotify.client_id, Some(&cfgs.spotify.client_secret))
-            .send()
-            .await?;
+        let token_response = spotify.send(
+            spotify.token_request()
+                .basic_auth(&cfgs.spotify.client_id, Some(&cfgs.spotify.client_secret)),
+            true
+        ).await?;
         assert!(token_response.status().is_success());
 
         let token: serde_json::Value = token_response.json().await?;
@@ -80,9 +99,8 @@ mod tests {
         println!("bearer: {bearer}");
 
         // Breathe Deeper -  Tame Impala, Lil Yatchy
-        let track_response = spotify.track("6GtOsEzNUhJghrIf6UTbRV", bearer)
-            .send()
-            .await?;
+        let track_id = ids::SpotifyTrackId::parse("6GtOsEzNUhJghrIf6UTbRV")?;
+        let track_response = spotify.send(spotify.track(&track_id, bearer), true).await?;
         assert!(track_response.status().is_success());
 
         let track: serde_json::Value = track_response.json().await?;
@@ -105,9 +123,8 @@ mod tests {
         let musicbrainz = fetch::MusicBrainzClient::new(
             &cfgs.http, &cfgs.identity, &cfgs.musicbrainz)?;
 
-        let response = musicbrainz.lookup_isrc("AUUM71900929")
-            .send()
-            .await?;
+        let isrc = ids::Isrc::parse("AUUM71900929")?;
+        let response = musicbrainz.send(musicbrainz.lookup_isrc(&isrc), true).await?;
         assert!(response.status().is_success());
 
         let isrc: serde_json::Value = response.json().await?; 
@@ -134,10 +151,11 @@ mod tests {
         let cfgs = config::load_config()?;
         let spotify = fetch::SpotifyClient::new(&cfgs.http, &cfgs.spotify)?;
 
-        let token_response = spotify.token_request()
-            .basic_auth(&cfgs.spotify.client_id, Some(&cfgs.spotify.client_secret))
-            .send()
-            .await?;
+        let token_response = spotify.send(
+            spotify.token_request()
+                .basic_auth(&cfgs.spotify.client_id, Some(&cfgs.spotify.client_secret)),
+            true
+        ).await?;
         assert!(token_response.status().is_success());
 
         let token: serde_json::Value = token_response.json().await?;
@@ -147,17 +165,20 @@ mod tests {
         println!("bearer: {bearer}");
 
         // Breathe Deeper -  Tame Impala, Lil Yatchy
-        let track_response = spotify.track("6GtOsEzNUhJghrIf6UTbRV", bearer)
-            .send()
-            .await?;
+        let track_id = ids::SpotifyTrackId::parse("6GtOsEzNUhJghrIf6UTbRV")?;
+        let track_response = spotify.send(spotify.track(&track_id, bearer), true).await?;
         assert!(track_response.status().is_success());
 
         let track_json: serde_json::Value = track_response.json().await?;
         println!("track: {}", serde_json::to_string_pretty(&track_json)?);
 
         let input = crate::persistent::SpotifyTrack {
-            spotify_id: Some(track_json["id"].as_str().unwrap().to_string()),
-            isrc: track_json["external_ids"]["isrc"].as_str().map(str::to_string),
+            spotify_id: Some(ids::SpotifyTrackId::parse(track_json["id"].as_str().unwrap())?
+                .into_owned()),
+            isrc: track_json["external_ids"]["isrc"].as_str()
+                .map(ids::Isrc::parse)
+                .transpose()?
+                .map(ids::Isrc::into_owned),
             title: track_json["name"].as_str().unwrap().to_string(),
             artist_all: track_json["artists"].as_array()
                 .unwrap()
@@ -4,16 +4,26 @@
 //!
 
 use std::str::FromStr;
+use std::time::Duration;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use sqlx::{sqlite::SqlitePoolOptions, sqlite::SqliteConnectOptions, Pool, Row, Sqlite};
-use uuid::Uuid; 
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+use uuid::Uuid;
 use crate::errors::CrawlerError;
+use crate::ids::{Isrc, Mbid, SpotifyTrackId};
+
+/// Fallback wakeup for `claim_next` so a job that was reaped or retried
+/// (and so never fired a fresh `Notify`) is still picked up in bounded time.
+const CLAIM_FALLBACK_POLL: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SpotifyTrack {
-    pub spotify_id: Option<String>, 
-    pub isrc: Option<String>, 
+    pub spotify_id: Option<SpotifyTrackId<'static>>,
+    pub isrc: Option<Isrc<'static>>,
     pub title: String, 
     pub artist_all: Vec<String>, 
     pub album: Option<String>, 
@@ -47,10 +57,14 @@ impl JobType {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JobStatus {
-    Pending, 
-    Active, 
-    Done, 
-    Failed
+    Pending,
+    Active,
+    Done,
+    Failed,
+    /// Exhausted `max_attempts`, dead-lettered by `dead_letter_job`, or
+    /// quarantined by `claim_one_job` for an unrecognized `kind`; parked for
+    /// operator inspection/replay.
+    Dead
 }
 
 impl JobStatus {
@@ -59,7 +73,8 @@ impl JobStatus {
             JobStatus::Pending => "pending",
             JobStatus::Active  => "active",
             JobStatus::Done    => "done",
-            JobStatus::Failed  => "failed"
+            JobStatus::Failed  => "failed",
+            JobStatus::Dead    => "dead"
         }
     }
     pub fn parse(s: &str) -> Option<JobStatus> {
@@ -68,32 +83,141 @@ impl JobStatus {
             "active"  => Some(JobStatus::Active),
             "done"    => Some(JobStatus::Done),
             "failed"  => Some(JobStatus::Failed),
-            _ => None 
+            "dead"    => Some(JobStatus::Dead),
+            _ => None
         }
     }
 }
 
+/// Default retry budget for a freshly-enqueued job; see `retry_job`.
+const DEFAULT_MAX_ATTEMPTS: i64 = 5;
+/// Upper bound on the exponential retry backoff, so a `base_delay` misconfigured
+/// too high can't park a job for an unreasonable amount of time.
+const MAX_RETRY_DELAY_SECS: i64 = 3_600;
+
 #[derive(Debug, Clone)]
 pub struct Job {
     pub job_id: i64,
-    pub track_id: String, 
+    pub track_id: String,
     pub kind: JobType,
     pub attempt: i64
 }
 
+/// A job parked in the `'dead'` status (retry budget exhausted,
+/// dead-lettered, or quarantined for an unrecognized `kind`), returned by `list_dead_jobs` so an operator can see why it died before
+/// deciding whether to `requeue_dead` it.
+#[derive(Debug, Clone)]
+pub struct DeadJob {
+    pub job_id: i64,
+    pub track_id: String,
+    pub kind: JobType,
+    pub attempt: i64,
+    pub last_error: Option<String>
+}
+
 #[derive(Debug, Clone)]
 pub struct Track {
-    pub id: String, 
-    pub spotify_id: Option<String>, 
-    pub isrc: Option<String>, 
-    pub mb_recording_id: Option<String>, 
-    pub linked_ok: bool, 
+    pub id: String,
+    pub title: Option<String>,
+    pub artist_all: Vec<String>,
+    pub spotify_id: Option<SpotifyTrackId<'static>>,
+    pub isrc: Option<Isrc<'static>>,
+    pub mb_recording_id: Option<Mbid<'static>>,
+    pub linked_ok: bool,
     pub features_ok: bool,
-    pub updated_at: i64 
+    pub updated_at: i64
+}
+
+impl Track {
+    pub fn first_artist(&self) -> &str {
+        self.artist_all
+            .first()
+            .map(String::as_str)
+            .unwrap_or("unknown")
+    }
+}
+
+/// Wakes a `claim_next` waiter as soon as a job of its `JobType` is
+/// enqueued, so workers don't have to busy-poll `claim_one_job`. One
+/// `Notify` per `JobType` so a burst of `Link` jobs doesn't also wake
+/// `Features` workers.
+pub struct JobNotifier {
+    link: Notify,
+    features: Notify
+}
+
+impl JobNotifier {
+    pub fn new() -> Self {
+        Self { link: Notify::new(), features: Notify::new() }
+    }
+
+    fn for_kind(&self, kind: JobType) -> &Notify {
+        match kind {
+            JobType::Link => &self.link,
+            JobType::Features => &self.features
+        }
+    }
+}
+
+impl Default for JobNotifier {
+    fn default() -> Self { Self::new() }
+}
+
+/// How `Persistent::connect` should obtain its `Pool<Sqlite>`.
+pub enum ConnectionSource {
+    /// Open a new pool against `url` with the given sizing.
+    Fresh {
+        url: String,
+        max_connections: u32,
+        /// Passed to `SqliteConnectOptions::disable_statement_logging`;
+        /// useful for quieting sqlx's default per-query log lines in tests.
+        disable_statement_logging: bool
+    },
+    /// Adopt a pool the caller already owns, e.g. one shared across
+    /// subsystems or set up by an integration test.
+    Existing(Pool<Sqlite>)
 }
 
 pub struct Persistent {
-    pool: Pool<Sqlite>
+    pool: Pool<Sqlite>,
+    notifier: JobNotifier
+}
+
+/// Per-`JobStatus` counts for a single `JobType`, as returned by `stats`.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct JobTypeStats {
+    pub pending: i64,
+    pub active: i64,
+    pub done: i64,
+    pub failed: i64,
+    pub dead: i64
+}
+
+impl JobTypeStats {
+    fn add(&mut self, status: JobStatus, n: i64) {
+        match status {
+            JobStatus::Pending => self.pending += n,
+            JobStatus::Active  => self.active += n,
+            JobStatus::Done    => self.done += n,
+            JobStatus::Failed  => self.failed += n,
+            JobStatus::Dead    => self.dead += n
+        }
+    }
+}
+
+/// A point-in-time snapshot of queue/progress state, serializable as JSON so
+/// a thin web handler can expose it for dashboards/health checks without
+/// callers having to query the SQLite file by hand.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrawlStats {
+    pub link_jobs: JobTypeStats,
+    pub features_jobs: JobTypeStats,
+    pub total_tracks: i64,
+    pub linked_ok: i64,
+    pub features_ok: i64,
+    /// `created_at` of the oldest still-pending job, so a caller can
+    /// compute queue age; `None` when the pending queue is empty.
+    pub oldest_pending_created_at: Option<i64>
 }
 
 impl Persistent {
@@ -125,17 +249,19 @@ impl Persistent {
         sqlx::query(
             r"
             CREATE TABLE IF NOT EXISTS jobs (
-              job_id      INTEGER PRIMARY KEY AUTOINCREMENT,
-              track_id    TEXT NOT NULL,
-              kind        TEXT NOT NULL CHECK (kind IN ('link','features')),
-              status      TEXT NOT NULL CHECK (status IN (
+              job_id          INTEGER PRIMARY KEY AUTOINCREMENT,
+              track_id        TEXT NOT NULL,
+              kind            TEXT NOT NULL CHECK (kind IN ('link','features')),
+              status          TEXT NOT NULL CHECK (status IN (
                   'pending','active',
-                  'done','failed')
+                  'done','failed','dead')
                   ) DEFAULT 'pending',
-              attempt     INTEGER NOT NULL DEFAULT 0,
-              last_error  TEXT,
-              created_at  INTEGER NOT NULL,
-              updated_at  INTEGER NOT NULL,
+              attempt         INTEGER NOT NULL DEFAULT 0,
+              max_attempts    INTEGER NOT NULL DEFAULT 5,
+              next_attempt_at INTEGER NOT NULL DEFAULT 0,
+              last_error      TEXT,
+              created_at      INTEGER NOT NULL,
+              updated_at      INTEGER NOT NULL,
               UNIQUE(track_id, kind),
               FOREIGN KEY(track_id) REFERENCES tracks(id) ON DELETE CASCADE
             );
@@ -152,33 +278,71 @@ impl Persistent {
 
         sqlx::query(
             "CREATE INDEX IF NOT EXISTS idx_tracks_mbid ON tracks(mb_recording_id);"
-        ).execute(pool).await?; 
+        ).execute(pool).await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS raw_files (
+              track_id    TEXT NOT NULL,
+              source      TEXT NOT NULL,
+              subtype     TEXT NOT NULL,
+              key         TEXT NOT NULL,
+              rel_path    TEXT NOT NULL,
+              created_at  INTEGER NOT NULL,
+              UNIQUE(track_id, source, subtype),
+              FOREIGN KEY(track_id) REFERENCES tracks(id) ON DELETE CASCADE
+            );
+            "
+        ).execute(pool).await?;
 
         Ok(())
-    } 
+    }
 
+    /// Convenience wrapper over `connect` for the common case of opening a
+    /// fresh pool against `database_url` with the default sizing.
     pub async fn init(database_url: &str) -> Result<Self, CrawlerError> {
-        let is_memory = database_url == "sqlite::memory:";
-
-        let mut opts = SqliteConnectOptions::from_str(database_url)?
-            .create_if_missing(true);
-
-        // WAL is file-only; don’t set it for in-memory
-        if !is_memory {
-            opts = opts.journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
-                       .synchronous(sqlx::sqlite::SqliteSynchronous::Normal);
-        }
+        Self::connect(ConnectionSource::Fresh {
+            url: database_url.to_string(),
+            max_connections: 8,
+            disable_statement_logging: false,
+        }).await
+    }
 
-        let pool = SqlitePoolOptions::new()
-            .min_connections(1)
-            .max_connections(if is_memory {1} else {8})
-            .connect_with(opts)
-            .await?;
+    /// Builds (or adopts) a `Pool<Sqlite>` per `source` and ensures the
+    /// schema exists on it. `Existing` lets the crawler, a future HTTP
+    /// status server, and integration tests all share one pool instead of
+    /// each opening their own.
+    pub async fn connect(source: ConnectionSource) -> Result<Self, CrawlerError> {
+        let pool = match source {
+            ConnectionSource::Fresh { url, max_connections, disable_statement_logging } => {
+                let is_memory = url == "sqlite::memory:";
+
+                let mut opts = SqliteConnectOptions::from_str(&url)?
+                    .create_if_missing(true);
+
+                if disable_statement_logging {
+                    opts = opts.disable_statement_logging();
+                }
+
+                // WAL is file-only; don't set it for in-memory
+                if !is_memory {
+                    opts = opts.journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
+                               .synchronous(sqlx::sqlite::SqliteSynchronous::Normal);
+                }
+
+                SqlitePoolOptions::new()
+                    .min_connections(1)
+                    .max_connections(if is_memory {1} else {max_connections})
+                    .connect_with(opts)
+                    .await?
+            }
+            ConnectionSource::Existing(pool) => pool,
+        };
 
         // Always create schema right away
         Self::ensure_schema(&pool).await?;
 
-        Ok(Self { pool })
+        Ok(Self { pool, notifier: JobNotifier::new() })
     }
 
 
@@ -188,10 +352,11 @@ impl Persistent {
 
     pub async fn upsert_track(&self, track: &SpotifyTrack) -> 
         Result<(String, bool), CrawlerError> {
-        // ensure spotify_id is not None 
-        let id: &str = track 
-            .spotify_id 
-            .as_deref() 
+        // ensure spotify_id is not None
+        let id: &str = track
+            .spotify_id
+            .as_ref()
+            .map(SpotifyTrackId::as_str)
             .ok_or_else(|| CrawlerError::Db("missing spotify_id".into()))?;
 
         if let Some(existing) = self.get_track_id(id).await? {
@@ -225,10 +390,10 @@ impl Persistent {
                 let _ = sqlx::query(
                     "UPDATE tracks SET isrc = COALESCE(isrc, ?1) WHERE id = ?2;"
                 )
-                .bind(isrc)
+                .bind(isrc.as_str())
                 .bind(&existing)
                 .execute(&self.pool)
-                .await; 
+                .await;
             }
             return Ok((existing, false));
         }
@@ -244,8 +409,8 @@ impl Persistent {
             "
         )
         .bind(&id)
-        .bind(&track.spotify_id)
-        .bind(track.isrc.as_ref())
+        .bind(track.spotify_id.as_ref().map(SpotifyTrackId::as_str))
+        .bind(track.isrc.as_ref().map(Isrc::as_str))
         .bind(&track.title)
         .bind(serde_json::to_string(&track.artist_all)?)
         .bind(track.album.as_ref())
@@ -294,57 +459,80 @@ impl Persistent {
 
     pub async fn enqueue_job_if_missing(&self, track_id: &str, kind: JobType) ->
         Result<(), CrawlerError> {
-        sqlx::query(
+        let inserted = sqlx::query(
             r"
             INSERT OR IGNORE INTO jobs (
-            track_id, kind, status, 
-            attempt, created_at, updated_at
+            track_id, kind, status,
+            attempt, max_attempts, next_attempt_at, created_at, updated_at
             )
-            VALUES (?1, ?2, 'pending', 0, ?3, ?3);
+            VALUES (?1, ?2, 'pending', 0, ?3, ?4, ?4, ?4);
             "
         )
         .bind(track_id)
         .bind(kind.as_str())
+        .bind(DEFAULT_MAX_ATTEMPTS)
         .bind(Self::now())
         .execute(&self.pool)
-        .await?; 
+        .await?
+        .rows_affected();
+
+        if inserted > 0 {
+            self.notifier.for_kind(kind).notify_one();
+        }
         Ok(())
     }
 
-    pub async fn claim_one_job(&self, kind: JobType) -> 
+    pub async fn claim_one_job(&self, kind: JobType) ->
         Result<Option<Job>, CrawlerError> {
-        let mut tx = self.pool.begin().await?; 
+        let mut tx = self.pool.begin().await?;
+        let now = Self::now();
 
         let row = sqlx::query(
             r"
-            SELECT job_id, track_id, kind, attempt 
-              FROM jobs 
-            WHERE kind = ?1 AND status = 'pending'
-            ORDER BY created_at ASC 
+            SELECT job_id, track_id, kind, attempt
+              FROM jobs
+            WHERE kind = ?1 AND status = 'pending' AND next_attempt_at <= ?2
+            ORDER BY created_at ASC
             LIMIT 1;
             "
         )
         .bind(kind.as_str())
+        .bind(now)
         .fetch_optional(&mut *tx)
-        .await?; 
+        .await?;
 
         let Some(row) = row else {
-            tx.rollback().await?; 
+            tx.rollback().await?;
             return Ok(None);
         };
 
-        let job_id   = row.get::<i64, _>("job_id");
-        let track_id = row.get::<String, _>("track_id");
-        let kind     = row.get::<String, _>("kind");
-        let attempt  = row.get::<i64, _>("attempt");
-        let now      = Self::now();
+        let job_id    = row.get::<i64, _>("job_id");
+        let track_id  = row.get::<String, _>("track_id");
+        let kind_field = row.get::<String, _>("kind");
+        let attempt   = row.get::<i64, _>("attempt");
+
+        // A corrupt `kind` can't be claimed as real work; quarantine it in
+        // the same transaction instead of flipping it to 'active' forever,
+        // and surface exactly which row/value was rejected.
+        let Some(parsed_kind) = JobType::parse(&kind_field) else {
+            sqlx::query("UPDATE jobs SET status = 'dead', updated_at = ?1 WHERE job_id = ?2;")
+                .bind(now)
+                .bind(job_id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            return Err(CrawlerError::InvalidJob {
+                job_id,
+                detail: format!("unrecognized kind {kind_field:?}")
+            });
+        };
 
         let updated = sqlx::query(
             r"
-            UPDATE jobs 
-                SET status = 'active'
-                    attempt = attempt + 1 
-                    updated_at = ?1 
+            UPDATE jobs
+                SET status = 'active',
+                    attempt = attempt + 1,
+                    updated_at = ?1
                 WHERE job_id = ?2 AND status = 'pending';
             "
         )
@@ -355,16 +543,36 @@ impl Persistent {
         .rows_affected();
 
         if updated == 0 {
-            tx.rollback().await?; 
+            tx.rollback().await?;
             return Ok(None);
         }
-        
-        tx.commit().await?; 
 
-        let kind = JobType::parse(&kind).ok_or_else(
-            || CrawlerError::Parse("bad kind in DB".to_string())
-        )?;
-        Ok(Some(Job { job_id, track_id, kind, attempt }))
+        tx.commit().await?;
+
+        Ok(Some(Job { job_id, track_id, kind: parsed_kind, attempt: attempt + 1 }))
+    }
+
+    /// Push-based alternative to polling `claim_one_job` in a loop: parks on
+    /// `JobNotifier` until a job of `kind` is enqueued, attempts a claim, and
+    /// re-parks if it lost the race to another worker. Falls back to waking
+    /// every `CLAIM_FALLBACK_POLL` regardless, so jobs picked up via
+    /// `retry_job`/`reap_stale_jobs` (which don't fire a fresh notify) are
+    /// still found in bounded time. Returns `None` once `shutdown` fires.
+    pub async fn claim_next(&self, kind: JobType, shutdown: &CancellationToken) ->
+        Result<Option<Job>, CrawlerError> {
+        loop {
+            let notified = self.notifier.for_kind(kind).notified();
+
+            if let Some(job) = self.claim_one_job(kind).await? {
+                return Ok(Some(job));
+            }
+
+            tokio::select! {
+                _ = notified => {}
+                _ = sleep(CLAIM_FALLBACK_POLL) => {}
+                _ = shutdown.cancelled() => return Ok(None),
+            }
+        }
     }
 
     pub async fn complete_job(&self, job_id: i64) -> Result<(), CrawlerError> {
@@ -382,21 +590,149 @@ impl Persistent {
         Ok(())
     }
 
-    pub async fn fail_job(&self, job_id: i64, err: &str) -> Result<(), CrawlerError> {
+    /// Reports a job failure. While `attempt < max_attempts`, the job is put
+    /// back to `'pending'` with `next_attempt_at` pushed out by an
+    /// exponentially-growing, `MAX_RETRY_DELAY_SECS`-capped delay; once the
+    /// budget is exhausted it's handed off to `dead_letter_job` instead, so a
+    /// single 429 can't permanently drop a track but a truly broken one
+    /// doesn't retry forever.
+    pub async fn retry_job(&self, job_id: i64, err: &str, base_delay_secs: i64) ->
+        Result<(), CrawlerError> {
+        let row = sqlx::query(
+            "SELECT attempt, max_attempts FROM jobs WHERE job_id = ?1;"
+        )
+        .bind(job_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(());
+        };
+
+        let attempt      = row.get::<i64, _>("attempt");
+        let max_attempts = row.get::<i64, _>("max_attempts");
+
+        if attempt >= max_attempts {
+            return self.dead_letter_job(job_id, err).await;
+        }
+
+        let delay = base_delay_secs.max(1)
+            .saturating_mul(1_i64 << attempt.saturating_sub(1).min(20))
+            .min(MAX_RETRY_DELAY_SECS);
+        let now = Self::now();
+
+        sqlx::query(
+            r"
+            UPDATE jobs SET status = 'pending', next_attempt_at = ?1,
+                updated_at = ?1, last_error = ?2
+                WHERE job_id = ?3;
+            "
+        )
+        .bind(now + delay)
+        .bind(err)
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Permanently parks a job that has either exhausted its retries or hit
+    /// an error not worth retrying at all.
+    pub async fn dead_letter_job(&self, job_id: i64, err: &str) -> Result<(), CrawlerError> {
         sqlx::query(
-            "UPDATE jobs SET status='failed', updated_at = ?1, 
+            "UPDATE jobs SET status = 'dead', updated_at = ?1,
                 last_error = ?2 WHERE job_id = ?3;"
         )
         .bind(Self::now())
-        .bind(err) 
+        .bind(err)
         .bind(job_id)
         .execute(&self.pool)
-        .await?; 
+        .await?;
 
         Ok(())
     }
 
-    pub async fn ensure_track(&self, track: &SpotifyTrack) -> 
+    /// Lists jobs parked in the `'dead'` status for operator inspection.
+    pub async fn list_dead_jobs(&self) -> Result<Vec<DeadJob>, CrawlerError> {
+        let rows = sqlx::query(
+            r"
+            SELECT job_id, track_id, kind, attempt, last_error
+              FROM jobs WHERE status = 'dead'
+            ORDER BY updated_at DESC;
+            "
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|r| {
+                let job_id    = r.get::<i64, _>("job_id");
+                let track_id  = r.get::<String, _>("track_id");
+                let kind_str  = r.get::<String, _>("kind");
+                let attempt   = r.get::<i64, _>("attempt");
+                let last_error = r.try_get("last_error").ok();
+                let kind = JobType::parse(&kind_str).ok_or_else(
+                    || CrawlerError::InvalidJob {
+                        job_id, detail: format!("unrecognized kind {kind_str:?}")
+                    }
+                )?;
+                Ok(DeadJob { job_id, track_id, kind, attempt, last_error })
+            })
+            .collect()
+    }
+
+    /// Resurrects a dead job: resets its attempt budget and schedules it for
+    /// immediate reclaim by `claim_one_job`.
+    pub async fn requeue_dead(&self, job_id: i64) -> Result<(), CrawlerError> {
+        let now = Self::now();
+        sqlx::query(
+            r"
+            UPDATE jobs SET status = 'pending', attempt = 0,
+                next_attempt_at = ?1, updated_at = ?1, last_error = NULL
+                WHERE job_id = ?2 AND status = 'dead';
+            "
+        )
+        .bind(now)
+        .bind(job_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Recovers jobs stranded `'active'` by a worker that crashed between
+    /// `claim_one_job` and `complete_job`/`retry_job`. `updated_at` doubles
+    /// as the claim lease timestamp (`claim_one_job` bumps it on claim), so
+    /// no separate `claimed_at` column is needed: any `active` row whose
+    /// lease has outlived `lease_secs` is reset to `'pending'` with its
+    /// attempt count bumped, to be picked up again. Intended to run
+    /// periodically from a supervisor task.
+    pub async fn reap_stale_jobs(&self, lease_secs: i64) -> Result<u64, CrawlerError> {
+        let now = Self::now();
+        let cutoff = now - lease_secs;
+
+        let reaped = sqlx::query(
+            r"
+            UPDATE jobs SET status = 'pending', attempt = attempt + 1,
+                next_attempt_at = ?1, updated_at = ?1
+                WHERE status = 'active' AND updated_at < ?2;
+            "
+        )
+        .bind(now)
+        .bind(cutoff)
+        .execute(&self.pool)
+        .await?
+        .rows_affected();
+
+        if reaped > 0 {
+            warn!(reaped, lease_secs, "jobs.reap_stale");
+        }
+
+        Ok(reaped)
+    }
+
+    pub async fn ensure_track(&self, track: &SpotifyTrack) ->
         Result<String, CrawlerError> {
         let (track_id, _) = self.upsert_track(track).await?; 
         let linked: Option<i64> = sqlx::query_scalar(
@@ -433,25 +769,119 @@ impl Persistent {
         Result<Option<Track>, CrawlerError> {
         let row = sqlx::query(
             r"
-            SELECT id, spotify_id, isrc, mb_recording_id, linked_ok, features_ok,
-            updated_at
+            SELECT id, title, artist_all, spotify_id, isrc, mb_recording_id,
+            linked_ok, features_ok, updated_at
                 FROM tracks where id = ?1;
             "
         )
         .bind(track_id)
         .fetch_optional(&self.pool)
-        .await?; 
-
-        Ok(row.map(|r| Track {
-            id: r.get("id"),
-            spotify_id: r.try_get("spotify_id").ok(),
-            isrc: r.try_get("isrc").ok(),
-            mb_recording_id: r.try_get("mb_recording_id").ok(),
-            linked_ok: r.get::<i64, _>("linked_ok") == 1,
-            features_ok: r.get::<i64, _>("features_ok") == 1, 
-            updated_at: r.get("updated_at")
+        .await?;
+
+        Ok(row.map(|r| {
+            let artist_all_json: Option<String> = r.try_get("artist_all").ok();
+            let artist_all: Vec<String> = artist_all_json
+                .as_deref()
+                .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())
+                .unwrap_or_default();
+            let spotify_id: Option<String> = r.try_get("spotify_id").ok();
+            let isrc: Option<String> = r.try_get("isrc").ok();
+            let mb_recording_id: Option<String> = r.try_get("mb_recording_id").ok();
+
+            Track {
+                id: r.get("id"),
+                title: r.try_get("title").ok(),
+                artist_all,
+                spotify_id: spotify_id.as_deref()
+                    .and_then(|s| SpotifyTrackId::parse(s).ok())
+                    .map(SpotifyTrackId::into_owned),
+                isrc: isrc.as_deref()
+                    .and_then(|s| Isrc::parse(s).ok())
+                    .map(Isrc::into_owned),
+                mb_recording_id: mb_recording_id.as_deref()
+                    .and_then(|s| Mbid::parse(s).ok())
+                    .map(Mbid::into_owned),
+                linked_ok: r.get::<i64, _>("linked_ok") == 1,
+                features_ok: r.get::<i64, _>("features_ok") == 1,
+                updated_at: r.get("updated_at")
+            }
         }))
     }
+
+    /// Records where a raw provider response was written on disk, so a
+    /// later reprocessing pass can find it without re-fetching. `(track_id,
+    /// source, subtype)` is unique - re-indexing the same raw file is a
+    /// no-op rather than an error.
+    pub async fn index_raw_file(
+        &self, track_id: &str, source: &str, subtype: &str, key: &str, rel_path: &str
+    ) -> Result<(), CrawlerError> {
+        sqlx::query(
+            r"
+            INSERT OR IGNORE INTO raw_files (
+                track_id, source, subtype, key, rel_path, created_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6);
+            "
+        )
+        .bind(track_id)
+        .bind(source)
+        .bind(subtype)
+        .bind(key)
+        .bind(rel_path)
+        .bind(Self::now())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Snapshots queue/progress state for a status endpoint or dashboard.
+    pub async fn stats(&self) -> Result<CrawlStats, CrawlerError> {
+        let rows = sqlx::query(
+            "SELECT kind, status, COUNT(*) as n FROM jobs GROUP BY kind, status;"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut link_jobs = JobTypeStats::default();
+        let mut features_jobs = JobTypeStats::default();
+
+        for row in rows {
+            let kind   = row.get::<String, _>("kind");
+            let status = row.get::<String, _>("status");
+            let n      = row.get::<i64, _>("n");
+
+            let (Some(kind), Some(status)) = (JobType::parse(&kind), JobStatus::parse(&status))
+                else { continue };
+
+            match kind {
+                JobType::Link => link_jobs.add(status, n),
+                JobType::Features => features_jobs.add(status, n)
+            }
+        }
+
+        let (total_tracks, linked_ok, features_ok) = sqlx::query_as::<_, (i64, i64, i64)>(
+            "SELECT COUNT(*), COALESCE(SUM(linked_ok), 0), COALESCE(SUM(features_ok), 0)
+               FROM tracks;"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let oldest_pending_created_at: Option<i64> = sqlx::query_scalar(
+            "SELECT MIN(created_at) FROM jobs WHERE status = 'pending';"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(CrawlStats {
+            link_jobs,
+            features_jobs,
+            total_tracks,
+            linked_ok,
+            features_ok,
+            oldest_pending_created_at
+        })
+    }
 }
 
 
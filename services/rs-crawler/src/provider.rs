@@ -0,0 +1,161 @@
+//!
+//! src/provider.rs  Andrew Belles  Sept 15th, 2025
+//!
+//! Defines a swappable `MetadataProvider` trait over the concrete HTTP
+//! clients in `fetch.rs`, plus a `MockProvider` backed by on-disk fixtures
+//! so the crawl pipeline and the sink extractors can be exercised in tests
+//! without live network access or API keys.
+//!
+
+use std::{collections::HashMap, path::{Path, PathBuf}};
+
+use async_trait::async_trait;
+
+use crate::errors::CrawlerError;
+use crate::fetch::{AcousticBrainzClient, MusicBrainzClient, SpotifyClient};
+use crate::ids::{Isrc, Mbid, SpotifyTrackId};
+
+/// Abstracts the lookup/search operations the crawl pipeline needs so tests
+/// can swap in canned fixtures instead of hitting Spotify/MusicBrainz.
+#[async_trait]
+pub trait MetadataProvider: Send + Sync {
+    async fn track(&self, id: &SpotifyTrackId<'_>, bearer: &str) -> Result<serde_json::Value, CrawlerError>;
+    async fn lookup_isrc(&self, isrc: &Isrc<'_>) -> Result<serde_json::Value, CrawlerError>;
+    async fn search_recording(&self, lucene: &str, limit: u32, offset: u32) -> Result<serde_json::Value, CrawlerError>;
+    async fn features(&self, mbid: &Mbid<'_>, level: &str) -> Result<serde_json::Value, CrawlerError>;
+    async fn track_top_tags(&self, artist: &str, track: &str) -> Result<serde_json::Value, CrawlerError>;
+}
+
+async fn read_json(resp: reqwest::Response) -> Result<serde_json::Value, CrawlerError> {
+    if !resp.status().is_success() {
+        return Err(CrawlerError::Http(format!("status {}", resp.status())));
+    }
+    Ok(resp.json::<serde_json::Value>().await?)
+}
+
+/// Live implementation backed by the real `fetch.rs` clients. Every request
+/// goes through the client's `send()`, so rate-limiting and retry/backoff
+/// (see `ratelimit.rs`) apply uniformly across providers.
+pub struct LiveProvider {
+    pub spotify: SpotifyClient,
+    pub musicbrainz: MusicBrainzClient,
+    pub acousticbrainz: AcousticBrainzClient,
+    pub lastfm: crate::fetch::LastFmClient,
+}
+
+#[async_trait]
+impl MetadataProvider for LiveProvider {
+    async fn track(&self, id: &SpotifyTrackId<'_>, bearer: &str) -> Result<serde_json::Value, CrawlerError> {
+        read_json(self.spotify.send(self.spotify.track(id, bearer), true).await?).await
+    }
+
+    async fn lookup_isrc(&self, isrc: &Isrc<'_>) -> Result<serde_json::Value, CrawlerError> {
+        read_json(self.musicbrainz.send(self.musicbrainz.lookup_isrc(isrc), true).await?).await
+    }
+
+    async fn search_recording(&self, lucene: &str, limit: u32, offset: u32) -> Result<serde_json::Value, CrawlerError> {
+        read_json(
+            self.musicbrainz.send(self.musicbrainz.search_recording(lucene, limit, offset), true).await?
+        ).await
+    }
+
+    async fn features(&self, mbid: &Mbid<'_>, level: &str) -> Result<serde_json::Value, CrawlerError> {
+        read_json(self.acousticbrainz.send(self.acousticbrainz.features(mbid, level), true).await?).await
+    }
+
+    async fn track_top_tags(&self, artist: &str, track: &str) -> Result<serde_json::Value, CrawlerError> {
+        read_json(self.lastfm.send(self.lastfm.track_top_tags(artist, track), true).await?).await
+    }
+}
+
+/// Deterministic provider that reads canned fixtures from disk instead of
+/// hitting any upstream API. Fixtures are looked up by key under
+/// `{fixture_dir}/{operation}/{key}.json`; missing files are treated as a
+/// `NotFound` error so callers exercise the same failure paths as the live
+/// provider.
+pub struct MockProvider {
+    fixture_dir: PathBuf,
+    overrides: HashMap<String, serde_json::Value>,
+}
+
+impl MockProvider {
+    pub fn new(fixture_dir: impl AsRef<Path>) -> Self {
+        Self { fixture_dir: fixture_dir.as_ref().to_path_buf(), overrides: HashMap::new() }
+    }
+
+    /// Registers an in-memory fixture, taking priority over the on-disk one.
+    pub fn with_fixture(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.overrides.insert(key.into(), value);
+        self
+    }
+
+    fn load(&self, operation: &str, key: &str) -> Result<serde_json::Value, CrawlerError> {
+        let override_key = format!("{operation}/{key}");
+        if let Some(v) = self.overrides.get(&override_key) {
+            return Ok(v.clone());
+        }
+
+        let path = self.fixture_dir.join(operation).join(format!("{key}.json"));
+        let bytes = std::fs::read(&path)
+            .map_err(|_| CrawlerError::NotFound(format!("fixture missing: {}", path.display())))?;
+        serde_json::from_slice(&bytes).map_err(CrawlerError::from)
+    }
+}
+
+#[async_trait]
+impl MetadataProvider for MockProvider {
+    async fn track(&self, id: &SpotifyTrackId<'_>, _bearer: &str) -> Result<serde_json::Value, CrawlerError> {
+        self.load("track", id.as_str())
+    }
+
+    async fn lookup_isrc(&self, isrc: &Isrc<'_>) -> Result<serde_json::Value, CrawlerError> {
+        self.load("isrc", isrc.as_str())
+    }
+
+    async fn search_recording(&self, lucene: &str, _limit: u32, _offset: u32) -> Result<serde_json::Value, CrawlerError> {
+        self.load("search_recording", &Self::sanitize(lucene))
+    }
+
+    async fn features(&self, mbid: &Mbid<'_>, level: &str) -> Result<serde_json::Value, CrawlerError> {
+        self.load("features", &format!("{}_{level}", mbid.as_str()))
+    }
+
+    async fn track_top_tags(&self, artist: &str, track: &str) -> Result<serde_json::Value, CrawlerError> {
+        self.load("toptags", &Self::sanitize(&format!("{artist}_{track}")))
+    }
+}
+
+impl MockProvider {
+    fn sanitize(key: &str) -> String {
+        key.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+}
+
+/// Returns `NotFound` for every operation; useful as a provider placeholder
+/// when a pipeline stage should be exercised without any real response.
+pub struct NullProvider;
+
+#[async_trait]
+impl MetadataProvider for NullProvider {
+    async fn track(&self, _id: &SpotifyTrackId<'_>, _bearer: &str) -> Result<serde_json::Value, CrawlerError> {
+        Err(CrawlerError::NotFound("NullProvider::track".into()))
+    }
+
+    async fn lookup_isrc(&self, _isrc: &Isrc<'_>) -> Result<serde_json::Value, CrawlerError> {
+        Err(CrawlerError::NotFound("NullProvider::lookup_isrc".into()))
+    }
+
+    async fn search_recording(&self, _lucene: &str, _limit: u32, _offset: u32) -> Result<serde_json::Value, CrawlerError> {
+        Err(CrawlerError::NotFound("NullProvider::search_recording".into()))
+    }
+
+    async fn features(&self, _mbid: &Mbid<'_>, _level: &str) -> Result<serde_json::Value, CrawlerError> {
+        Err(CrawlerError::NotFound("NullProvider::features".into()))
+    }
+
+    async fn track_top_tags(&self, _artist: &str, _track: &str) -> Result<serde_json::Value, CrawlerError> {
+        Err(CrawlerError::NotFound("NullProvider::track_top_tags".into()))
+    }
+}
@@ -0,0 +1,222 @@
+//!
+//! src/ratelimit.rs  Andrew Belles  Sept 16th, 2025
+//!
+//! Shared rate-limiting and retry/backoff layer. `fetch.rs`'s docstring has
+//! long promised "handling retries" without actually doing it; this module
+//! is the one place every `*Client` routes a `RequestBuilder` through
+//! before executing it, so MusicBrainz/AcousticBrainz's ~1 req/s-per-IP
+//! politeness limit and Spotify/Last.fm's `429 Retry-After` are both
+//! honored uniformly.
+//!
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+use tracing::warn;
+
+use crate::config::RetryConfig;
+use crate::errors::CrawlerError;
+
+/// Token-bucket limiter: refills `rate_per_sec` tokens a second up to
+/// `burst` capacity, and `wait()`s until a token is available.
+pub struct RateLimiter {
+    rate_per_sec: f64,
+    burst: f64,
+    tokens: Mutex<f64>,
+    last_refill: Mutex<Instant>,
+    retry_count: AtomicU64,
+    limited_count: AtomicU64,
+}
+
+impl RateLimiter {
+    pub fn new(rate_per_sec: f32, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        Self {
+            rate_per_sec: rate_per_sec.max(0.01) as f64,
+            burst,
+            tokens: Mutex::new(burst),
+            last_refill: Mutex::new(Instant::now()),
+            retry_count: AtomicU64::new(0),
+            limited_count: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        loop {
+            {
+                let mut tokens = self.tokens.lock().await;
+                let mut last = self.last_refill.lock().await;
+                let elapsed = last.elapsed().as_secs_f64();
+                *tokens = (*tokens + elapsed * self.rate_per_sec).min(self.burst);
+                *last = Instant::now();
+
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+            }
+            let wait_secs = (1.0 / self.rate_per_sec).max(0.001);
+            sleep(Duration::from_secs_f64(wait_secs)).await;
+        }
+    }
+
+    pub fn retries_observed(&self) -> u64 {
+        self.retry_count.load(Ordering::Relaxed)
+    }
+
+    pub fn limited_observed(&self) -> u64 {
+        self.limited_count.load(Ordering::Relaxed)
+    }
+}
+
+fn generate_backoff(base: Duration, attempt: u32, jitter: bool) -> Duration {
+    let exp = base.as_millis() as u64 * (1_u64 << attempt.min(6));
+    let with_jitter = if jitter {
+        let extra = rand::thread_rng().gen_range(0..=base.as_millis() as u64 / 2 + 1);
+        exp + extra
+    } else {
+        exp
+    };
+    Duration::from_millis(with_jitter)
+}
+
+/// Parses a `Retry-After` header value, which per RFC 9110 is either a
+/// delta-seconds integer or an IMF-fixdate HTTP-date
+/// (`Sun, 06 Nov 1994 08:49:37 GMT`).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let target = parse_imf_fixdate(value)?;
+    Some(Duration::from_secs(target.saturating_sub(unix_now())))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Days since the Unix epoch for a given civil (proleptic Gregorian) date,
+/// per Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as i64;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Parses the `Sun, 06 Nov 1994 08:49:37 GMT` format servers actually send;
+/// the obsolete RFC 850/asctime forms aren't worth supporting.
+fn parse_imf_fixdate(value: &str) -> Option<u64> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    if parts.len() != 6 || parts[5] != "GMT" {
+        return None;
+    }
+
+    let day: i64 = parts[1].parse().ok()?;
+    let month = match parts[2] {
+        "Jan" => 1, "Feb" => 2, "Mar" => 3, "Apr" => 4,
+        "May" => 5, "Jun" => 6, "Jul" => 7, "Aug" => 8,
+        "Sep" => 9, "Oct" => 10, "Nov" => 11, "Dec" => 12,
+        _ => return None,
+    };
+    let year: i64 = parts[3].parse().ok()?;
+
+    let mut clock = parts[4].split(':');
+    let hour: i64 = clock.next()?.parse().ok()?;
+    let min: i64 = clock.next()?.parse().ok()?;
+    let sec: i64 = clock.next()?.parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + hour * 3_600 + min * 60 + sec;
+    u64::try_from(secs).ok()
+}
+
+fn retry_after_from(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+/// Sends `request` through `limiter` (if any), retrying on 429/5xx/transport
+/// errors according to `retry`. Only call this for idempotent requests
+/// (GETs, or the Spotify client-credentials token POST) — set
+/// `idempotent = false` to disable retries on non-idempotent requests.
+pub async fn send_with_retry(
+    request: reqwest::RequestBuilder,
+    retry: &RetryConfig,
+    limiter: Option<&RateLimiter>,
+    idempotent: bool,
+) -> Result<reqwest::Response, CrawlerError> {
+    let mut attempt: u32 = 0;
+
+    loop {
+        if let Some(limiter) = limiter {
+            limiter.acquire().await;
+        }
+
+        let cloned = request
+            .try_clone()
+            .ok_or_else(|| CrawlerError::Http("non-cloneable request".to_string()))?;
+
+        let response = cloned.send().await;
+
+        match response {
+            Ok(resp) if resp.status().is_success() => return Ok(resp),
+            Ok(resp) => {
+                let status = resp.status();
+                let retryable = idempotent
+                    && (status.as_u16() == 429 || status.as_u16() == 503 || status.is_server_error());
+
+                if !retryable || attempt >= retry.max_attempts as u32 {
+                    return Err(CrawlerError::Http(format!(
+                        "status {status} after {attempt} retries"
+                    )));
+                }
+
+                if let Some(limiter) = limiter {
+                    if status.as_u16() == 429 {
+                        limiter.limited_count.fetch_add(1, Ordering::Relaxed);
+                    }
+                    limiter.retry_count.fetch_add(1, Ordering::Relaxed);
+                }
+
+                let server_hint = retry.respect_retry_after
+                    .then(|| retry_after_from(resp.headers()))
+                    .flatten()
+                    .map(|d| d.min(retry.retry_after_cap));
+                let backoff = server_hint.unwrap_or_else(|| {
+                    generate_backoff(retry.base_backoff, attempt, retry.jitter)
+                });
+                warn!(
+                    status = %status, backoff_ms = backoff.as_millis(),
+                    server_hint = server_hint.is_some(), "http.retry"
+                );
+                sleep(backoff).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                if !idempotent || attempt >= retry.max_attempts as u32 {
+                    return Err(e.into());
+                }
+                if let Some(limiter) = limiter {
+                    limiter.retry_count.fetch_add(1, Ordering::Relaxed);
+                }
+                let backoff = generate_backoff(retry.base_backoff, attempt, retry.jitter);
+                warn!(backoff_ms = backoff.as_millis(), "http.retry.transport");
+                sleep(backoff).await;
+                attempt += 1;
+            }
+        }
+    }
+}
@@ -13,11 +13,12 @@ use crate::errors::CrawlerError;
 
 #[derive(Debug, Clone, Copy)]
 pub enum RawType {
-    SpotifyTrack, 
-    MusicBrainzRecording,  
-    ABHighLevel, 
-    ABLowLevel, 
-    LastFmTopTags 
+    SpotifyTrack,
+    MusicBrainzRecording,
+    ABHighLevel,
+    ABLowLevel,
+    LastFmTopTags,
+    YouTubeSearch,
 }
 
 pub struct DiskZstdSink {
@@ -35,6 +36,7 @@ impl DiskZstdSink {
 
         match kind {
             RawType::SpotifyTrack => Self::prune_spotify_track(&mut json),
+            RawType::YouTubeSearch => Self::prune_youtube_video(&mut json),
             _ => {},
         }
 
@@ -87,8 +89,10 @@ impl DiskZstdSink {
                 ["raw", "acousticbrainz", "high-level", &end],
             RawType::ABLowLevel => 
                 ["raw", "acousticbrainz", "low-level", &end],
-            RawType::LastFmTopTags => 
+            RawType::LastFmTopTags =>
                 ["raw", "lastfm", "toptags", &end],
+            RawType::YouTubeSearch =>
+                ["raw", "youtube", "search", &end],
         }.into_iter().collect()
     }
 
@@ -183,15 +187,81 @@ impl DiskZstdSink {
         }
 
         if let Some(isrc) = ext_isrc {
-            let mut ext = Map::new(); 
+            let mut ext = Map::new();
             ext.insert("isrc".into(), Value::String(isrc));
             root.insert("external_ids".into(), Value::Object(ext));
         }
 
+        if let Some(markets) = Self::sorted_markets(v) {
+            root.insert(
+                "available_markets".into(),
+                Value::Array(markets.into_iter().map(Value::String).collect()),
+            );
+        }
+
+        *v = Value::Object(root);
+    }
+
+    /// Reads `available_markets` off the track, falling back to
+    /// `album.available_markets` when the track-level list is absent (the
+    /// same fallback Spotify's own catalogue uses), and returns it sorted
+    /// and deduped so callers can binary-search it.
+    fn sorted_markets(v: &Value) -> Option<Vec<String>> {
+        let raw = v.get("available_markets")
+            .and_then(Value::as_array)
+            .or_else(|| v.pointer("/album/available_markets").and_then(Value::as_array))?;
+
+        let mut markets: Vec<String> = raw.iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect();
+        markets.sort_unstable();
+        markets.dedup();
+        Some(markets)
+    }
+
+    /// Reports whether a track pruned by `prune_spotify_track` is playable
+    /// in `country` (an ISO-3166-1 alpha-2 code). Market lists are
+    /// allowed-set semantics: a missing `available_markets` field means
+    /// "unknown, assume playable", while an explicit empty list means
+    /// globally unavailable.
+    pub fn is_available_in(pruned_track: &Value, country: &str) -> bool {
+        let Some(markets) = pruned_track.get("available_markets").and_then(Value::as_array) else {
+            return true;
+        };
+        if markets.is_empty() {
+            return false;
+        }
+        markets.binary_search_by(|m| {
+            m.as_str().unwrap_or_default().cmp(country)
+        }).is_ok()
+    }
+
+    /// Whitelists the fields needed for a `youtube_id` cross-reference out
+    /// of the canonical (top, by view count) Invidious search hit.
+    fn prune_youtube_video(v: &mut Value) {
+        let mut root = Map::new();
+
+        if let Some(x) = v.get("videoId").and_then(Value::as_str) {
+            root.insert("videoId".into(), Value::String(x.to_string()));
+        }
+        if let Some(x) = v.get("title").and_then(Value::as_str) {
+            root.insert("title".into(), Value::String(x.to_string()));
+        }
+        if let Some(x) = v.get("author").and_then(Value::as_str) {
+            root.insert("author".into(), Value::String(x.to_string()));
+        }
+        if let Some(x) = v.get("lengthSeconds").and_then(Value::as_i64) {
+            root.insert("lengthSeconds".into(), Value::Number(x.into()));
+        }
+        if let Some(x) = v.get("viewCount").and_then(Value::as_i64) {
+            root.insert("viewCount".into(), Value::Number(x.into()));
+        }
+
         *v = Value::Object(root);
     }
 
-    pub fn extract_high_level(v: &serde_json::Value) -> 
+    pub fn extract_high_level(v: &serde_json::Value) ->
         (Vec<(String, f64)>, Vec<(String, String)>) {
         let mut nums: Vec<(String, f64)> = Vec::new();
         let mut texts: Vec<(String, String)> = Vec::new();
@@ -0,0 +1,153 @@
+//!
+//! src/trigram.rs  Andrew Belles  Sept 17th, 2025
+//!
+//! Scores MusicBrainz-style candidates against a `TrackKey` using trigram
+//! Jaccard similarity, since titles/artists rarely match a query verbatim
+//! (punctuation, "feat." credits, casing) the way an ISRC does.
+//!
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::types::{CanonicalLink, Isrc, MbRecordingId, MbReleaseId, TrackKey};
+
+/// A candidate recording to score against a `TrackKey`; fields mirror
+/// `CanonicalLink`'s so a scored candidate converts into one directly.
+#[derive(Debug, Clone, Default)]
+pub struct Candidate {
+    pub mb_recording_id: String,
+    pub mb_release_id: Option<String>,
+    pub title: String,
+    pub artist_name: String,
+    pub duration_ms: Option<u32>,
+    pub isrc: Option<String>,
+}
+
+const TITLE_WEIGHT: f32 = 0.6;
+const ARTIST_WEIGHT: f32 = 0.4;
+const DURATION_BONUS: f32 = 0.05;
+const DURATION_TOL_MS: i64 = 2_000;
+
+/// Lowercases, folds common Latin diacritics to their ASCII base letter,
+/// strips remaining punctuation, and collapses whitespace.
+fn normalize(s: &str) -> String {
+    s.to_lowercase()
+        .chars()
+        .map(fold_diacritic)
+        .filter(|c| c.is_ascii_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Folds the common Latin-1/Latin Extended-A accented letters to their
+/// unaccented ASCII base; anything outside that range passes through
+/// unchanged (dropped later by the alphanumeric filter if not ASCII).
+fn fold_diacritic(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ñ' => 'n',
+        'ç' => 'c',
+        'ß' => 's',
+        other => other,
+    }
+}
+
+/// Builds the set of length-3 substrings of `s`, padded with two leading
+/// and one trailing space so short strings and word boundaries still
+/// contribute trigrams.
+fn trigrams(s: &str) -> HashSet<String> {
+    let padded: Vec<char> = format!("  {s} ").chars().collect();
+    if padded.len() < 3 {
+        return HashSet::new();
+    }
+    padded.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+/// `|A ∩ B| / |A ∪ B|` over the normalized trigram sets of `a` and `b`.
+fn trigram_similarity(a: &str, b: &str) -> f32 {
+    let a = trigrams(&normalize(a));
+    let b = trigrams(&normalize(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(&b).count() as f32;
+    let union = a.union(&b).count() as f32;
+    intersection / union
+}
+
+/// Computes a `[0.0, 1.0]` confidence for `candidate` against `key`: a
+/// title/artist trigram blend (0.6/0.4), a small bonus when durations are
+/// within `DURATION_TOL_MS` of each other, and a hard 1.0 when the ISRCs
+/// match exactly (ISRCs are authoritative; no amount of fuzzy mismatch
+/// should outweigh one).
+pub fn confidence(key: &TrackKey, candidate: &Candidate) -> f32 {
+    if let (Some(q), Some(c)) = (&key.isrc, &candidate.isrc) {
+        if q.0.eq_ignore_ascii_case(c) {
+            return 1.0;
+        }
+    }
+
+    let title_sim = key.title.as_deref()
+        .map(|t| trigram_similarity(t, &candidate.title))
+        .unwrap_or(0.0);
+    let artist_sim = key.artist_name.as_deref()
+        .map(|a| trigram_similarity(a, &candidate.artist_name))
+        .unwrap_or(0.0);
+
+    let mut score = TITLE_WEIGHT * title_sim + ARTIST_WEIGHT * artist_sim;
+
+    if let (Some(q), Some(c)) = (key.duration_ms, candidate.duration_ms) {
+        if (q as i64 - c as i64).abs() <= DURATION_TOL_MS {
+            score += DURATION_BONUS;
+        }
+    }
+
+    score.clamp(0.0, 1.0)
+}
+
+fn to_link(internal_track_uuid: Uuid, key: &TrackKey, candidate: &Candidate, score: f32) -> CanonicalLink {
+    CanonicalLink {
+        internal_track_uuid,
+        mb_recording_id: Some(MbRecordingId(candidate.mb_recording_id.clone())),
+        mb_release_id: candidate.mb_release_id.clone().map(MbReleaseId),
+        isrc: candidate.isrc.clone().map(Isrc).or_else(|| key.isrc.clone()),
+        spotify_track_id: key.spotify_id.clone(),
+        confidence: score,
+    }
+}
+
+/// Scores every candidate against `key` and returns `CanonicalLink`s sorted
+/// by descending confidence.
+pub fn rank(internal_track_uuid: Uuid, key: &TrackKey, candidates: &[Candidate]) -> Vec<CanonicalLink> {
+    let mut links: Vec<CanonicalLink> = candidates
+        .iter()
+        .map(|c| to_link(internal_track_uuid, key, c, confidence(key, c)))
+        .collect();
+    links.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    links
+}
+
+/// Returns the best-scoring candidate only if its confidence clears
+/// `threshold`, so callers only ever `set_mbid` a match they can trust and
+/// weak candidates are left unlinked rather than guessed at.
+pub fn best_candidate(
+    internal_track_uuid: Uuid,
+    key: &TrackKey,
+    candidates: &[Candidate],
+    threshold: f32,
+) -> Option<CanonicalLink> {
+    let best = rank(internal_track_uuid, key, candidates).into_iter().next()?;
+    if best.confidence >= threshold {
+        Some(best)
+    } else {
+        None
+    }
+}
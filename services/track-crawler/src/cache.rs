@@ -0,0 +1,130 @@
+//!
+//! src/cache.rs  Andrew Belles  Jan 8th, 2026
+//!
+//! Bounded, TTL'd in-memory lookup cache consulted by the link/features
+//! loops before they hit MusicBrainz/AcousticBrainz/Last.fm. The feed
+//! resurfaces the same ISRC or (title, artist) pair, and the same MBID's
+//! AcousticBrainz payloads, across different random year/offset windows
+//! often enough that re-resolving them is pure waste against
+//! MusicBrainz's 1.1s-gated rate limit and AcousticBrainz's shared
+//! instance. A miss is cached too, with a shorter TTL, so a track with no
+//! recording anywhere doesn't get re-queried every time it resurfaces.
+//!
+//! No dedicated LRU crate exists in this tree, so eviction is hand-rolled:
+//! a `VecDeque` tracks insertion order and the oldest entry is dropped
+//! once the cache is over capacity.
+//!
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+enum Cached<V> {
+    Found(V),
+    Missing,
+}
+
+struct Entry<V> {
+    value: Cached<V>,
+    expires_at: Instant,
+}
+
+struct State<V> {
+    entries: HashMap<String, Entry<V>>,
+    order: VecDeque<String>,
+}
+
+/// Outcome of `Cache::get`: whether `key` was cached at all, and if so,
+/// whether it resolved to a value or a cached "nothing found".
+pub enum Lookup<V> {
+    Found(V),
+    Missing,
+    Absent,
+}
+
+/// A bounded cache of upstream lookups, keyed by an arbitrary string (an
+/// ISRC, a (title, artist) pair, an MBID - whatever the caller resolves
+/// against). `positive_ttl` governs a real result; `missing_ttl` is
+/// shorter, since a cached miss is cheap to re-check but expensive to
+/// keep retrying forever if it turns out to be wrong.
+pub struct Cache<V> {
+    state: Mutex<State<V>>,
+    capacity: usize,
+    positive_ttl: Duration,
+    missing_ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<V: Clone> Cache<V> {
+    pub fn new(capacity: usize, positive_ttl: Duration, missing_ttl: Duration) -> Self {
+        Self {
+            state: Mutex::new(State { entries: HashMap::new(), order: VecDeque::new() }),
+            capacity,
+            positive_ttl,
+            missing_ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn get(&self, key: &str) -> Lookup<V> {
+        let mut state = self.state.lock().await;
+        let Some(entry) = state.entries.get(key) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return Lookup::Absent;
+        };
+
+        if entry.expires_at < Instant::now() {
+            state.entries.remove(key);
+            state.order.retain(|k| k != key);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return Lookup::Absent;
+        }
+
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        match &entry.value {
+            Cached::Found(v) => Lookup::Found(v.clone()),
+            Cached::Missing => Lookup::Missing,
+        }
+    }
+
+    pub async fn put_found(&self, key: String, value: V) {
+        self.insert(key, Cached::Found(value), self.positive_ttl).await;
+    }
+
+    pub async fn put_missing(&self, key: String) {
+        self.insert(key, Cached::Missing, self.missing_ttl).await;
+    }
+
+    async fn insert(&self, key: String, value: Cached<V>, ttl: Duration) {
+        let mut state = self.state.lock().await;
+        if !state.entries.contains_key(&key) {
+            state.order.push_back(key.clone());
+        }
+        state.entries.insert(key, Entry { value, expires_at: Instant::now() + ttl });
+
+        while state.entries.len() > self.capacity {
+            match state.order.pop_front() {
+                Some(oldest) => { state.entries.remove(&oldest); }
+                None => break,
+            }
+        }
+    }
+
+    pub fn snapshot(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
@@ -0,0 +1,476 @@
+//!
+//! src/config.rs  Andrew Belles  Sept 13th, 2025
+//!
+//! Defines configuration structs read from the environment at process
+//! start and handed down into `fetch`/`crawler`/`logging`.
+//!
+//!
+
+use std::time;
+use url::Url;
+
+use crate::errors::CrawlerError;
+
+pub const HTTP_TIMEOUT: u64 = 8000;
+pub const HTTP_CONNECT_TIMEOUT: u64 = 2000;
+pub const HTTP_POOL_MAX_IDLE: usize = 16;
+pub const HTTP_POOL_IDLE_TIMEOUT: u64 = 90000;
+pub const HTTP_MAX_REDIRECTS: u8 = 4;
+
+pub const RETRY_MAX_ATTEMPTS: u8 = 4;
+pub const RETRY_BASE_BACKOFF: u64 = 250;
+pub const RETRY_JITTER: bool = true;
+pub const RETRYABLE_STATUSES: [u16; 5] = [429, 500, 502, 503, 504];
+pub const RETRY_AFTER_CAP_SECS: u64 = 120;
+
+pub const DEFAULT_MAX_RPS: f32 = 5.0;
+pub const DEFAULT_BURST: u32 = 5;
+
+/// Token-bucket ceiling for a single provider: `max_rps` tokens refill per
+/// second up to `burst` capacity, enforced by `fetch::send_with_retry`'s
+/// per-host `RateLimiter`.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    pub max_rps: f32,
+    pub burst: u32,
+}
+
+fn env_rate_limit(rps_var: &str, burst_var: &str, default: RateLimitConfig) -> RateLimitConfig {
+    let max_rps = std::env::var(rps_var).ok()
+        .and_then(|s| s.parse::<f32>().ok())
+        .unwrap_or(default.max_rps);
+    let burst = std::env::var(burst_var).ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(default.burst);
+    RateLimitConfig { max_rps, burst }
+}
+
+fn env_check(s: &str) -> Result<String, CrawlerError> {
+    match std::env::var(s) {
+        Ok(v) if !v.trim().is_empty() => Ok(v),
+        _ => Err(CrawlerError::Config(format!("{s} was not set"))),
+    }
+}
+
+fn ensure_https(url: &Url) -> Result<(), String> {
+    if url.scheme() == "https" {
+        Ok(())
+    } else {
+        Err(format!("URL must be https: {url}"))
+    }
+}
+
+fn ensure_host(url: &Url, expected_host: &str) -> Result<(), String> {
+    match url.host_str() {
+        Some(h) if h.eq_ignore_ascii_case(expected_host) => Ok(()),
+        Some(h) => Err(
+            format!("Unexpected host for {url} (got {h}, expected {expected_host})")
+        ),
+        None => Err(format!("URL missing host: {url}"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AppEnv { Dev, Staging, Prod }
+
+/// Identity presented to MusicBrainz/AcousticBrainz, whose politeness
+/// policy requires a descriptive `User-Agent` on every request.
+#[derive(Debug, Clone)]
+pub struct IdentityConfig {
+    pub app_env: AppEnv,
+    pub mb_user_agent: String,
+}
+
+fn build_identity() -> Result<IdentityConfig, CrawlerError> {
+    let application   = env_check("APPLICATION")?;
+    let header        = env_check("MUSIC_BRAINZ_HEADER")?;
+    let mb_user_agent = format!("{application} {header}");
+
+    Ok( IdentityConfig { app_env: AppEnv::Dev, mb_user_agent } )
+}
+
+const REDACTED: &str = "***redacted***";
+
+#[derive(Clone)]
+pub struct SpotifyConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    pub token_url: Url,
+    pub api_base: Url,
+    pub rate_limit: RateLimitConfig,
+}
+
+impl std::fmt::Debug for SpotifyConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpotifyConfig")
+            .field("client_id", &self.client_id)
+            .field("client_secret", &REDACTED)
+            .field("token_url", &self.token_url)
+            .field("api_base", &self.api_base)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+fn build_spotify() -> Result<SpotifyConfig, CrawlerError> {
+    let client_id     = env_check("SPOTIFY_CLIENT_ID")?;
+    let client_secret = env_check("SPOTIFY_CLIENT_SECRET")?;
+
+    let token_url = std::env::var("SPOTIFY_TOKEN_URL")
+        .unwrap_or_else(|_| "https://accounts.spotify.com/api/token".to_string());
+    let api_base  = std::env::var("SPOTIFY_API_BASE")
+        .unwrap_or_else(|_| "https://api.spotify.com/v1/".to_string());
+
+    let token_url = Url::parse(&token_url)
+        .map_err(|_| CrawlerError::Config("SPOTIFY_TOKEN_URL invalid".to_string()))?;
+    let mut api_base = Url::parse(&api_base)
+        .map_err(|_| CrawlerError::Config("SPOTIFY_API_BASE invalid".to_string()))?;
+
+    ensure_https(&token_url).map_err(CrawlerError::Config)?;
+    ensure_https(&api_base).map_err(CrawlerError::Config)?;
+    ensure_host(&token_url, "accounts.spotify.com").map_err(CrawlerError::Config)?;
+    ensure_host(&api_base, "api.spotify.com").map_err(CrawlerError::Config)?;
+
+    if !api_base.path().ends_with('/') {
+        let mut path = api_base.path().to_string();
+        path.push('/');
+        api_base.set_path(&path);
+    }
+
+    let rate_limit = env_rate_limit(
+        "SPOTIFY_MAX_RPS", "SPOTIFY_BURST",
+        RateLimitConfig { max_rps: 10.0, burst: 20 },
+    );
+
+    Ok( SpotifyConfig { client_id, client_secret, token_url, api_base, rate_limit } )
+}
+
+/// MusicBrainz asks integrators to stay near 1 req/s per IP; `rate_limit`
+/// defaults to that and is what `fetch::send_with_retry` enforces per-host.
+#[derive(Debug, Clone)]
+pub struct MusicBrainzConfig {
+    pub base_url: Url,
+    pub rate_limit: RateLimitConfig,
+}
+
+fn build_musicbrainz() -> Result<MusicBrainzConfig, CrawlerError> {
+    let base_url = std::env::var("MB_BASE_URL")
+        .unwrap_or_else(|_| "https://musicbrainz.org/ws/2/".to_string());
+    let mut base_url = Url::parse(&base_url)
+        .map_err(|e| CrawlerError::Config(format!("MB_BASE_URL invalid {e}")))?;
+
+    ensure_https(&base_url).map_err(CrawlerError::Config)?;
+    ensure_host(&base_url, "musicbrainz.org").map_err(CrawlerError::Config)?;
+
+    if !base_url.path().ends_with('/') {
+        let mut path = base_url.path().to_string();
+        path.push('/');
+        base_url.set_path(&path);
+    }
+
+    let rate_limit = env_rate_limit(
+        "MB_MAX_RPS", "MB_BURST", RateLimitConfig { max_rps: 1.0, burst: 1 }
+    );
+
+    Ok( MusicBrainzConfig { base_url, rate_limit } )
+}
+
+/// AcousticBrainz shares MusicBrainz's ~1 req/s-per-IP politeness norm.
+#[derive(Debug, Clone)]
+pub struct AcousticBrainzConfig {
+    pub base_url: Url,
+    pub rate_limit: RateLimitConfig,
+}
+
+fn build_acousticbrainz() -> Result<AcousticBrainzConfig, CrawlerError> {
+    let base_url = std::env::var("AB_BASE_URL")
+        .unwrap_or_else(|_| "https://acousticbrainz.org/".to_string());
+    let base_url = Url::parse(&base_url)
+        .map_err(|e| CrawlerError::Config(format!("AB_BASE_URL invalid {e}")))?;
+
+    ensure_https(&base_url).map_err(CrawlerError::Config)?;
+
+    let rate_limit = env_rate_limit(
+        "AB_MAX_RPS", "AB_BURST", RateLimitConfig { max_rps: 1.0, burst: 1 }
+    );
+
+    Ok( AcousticBrainzConfig { base_url, rate_limit } )
+}
+
+#[derive(Clone)]
+pub struct LastFmConfig {
+    pub base_url: Url,
+    pub api_key: String,
+    pub rate_limit: RateLimitConfig,
+}
+
+impl std::fmt::Debug for LastFmConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LastFmConfig")
+            .field("base_url", &self.base_url)
+            .field("api_key", &REDACTED)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+fn build_lastfm() -> Result<LastFmConfig, CrawlerError> {
+    let api_key = env_check("LASTFM_API_KEY")?;
+    let base_url = Url::parse("https://ws.audioscrobbler.com/2.0/")
+        .map_err(|e| CrawlerError::Config(format!("lastfm base url invalid {e}")))?;
+
+    let rate_limit = env_rate_limit(
+        "LASTFM_MAX_RPS", "LASTFM_BURST", RateLimitConfig { max_rps: 5.0, burst: 5 }
+    );
+
+    Ok( LastFmConfig { base_url, api_key, rate_limit } )
+}
+
+/// Third-tier MBID fallback via AcoustID's fingerprint `lookup` endpoint,
+/// consulted only when both an ISRC lookup and a title/artist text search
+/// come back empty. AcoustID asks for at most ~3 req/s per client key.
+#[derive(Clone)]
+pub struct AcoustIdConfig {
+    pub base_url: Url,
+    pub api_key: String,
+    pub rate_limit: RateLimitConfig,
+}
+
+impl std::fmt::Debug for AcoustIdConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AcoustIdConfig")
+            .field("base_url", &self.base_url)
+            .field("api_key", &REDACTED)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
+
+fn build_acoustid() -> Result<AcoustIdConfig, CrawlerError> {
+    let api_key = env_check("ACOUSTID_API_KEY")?;
+    let base_url = Url::parse("https://api.acoustid.org/v2/")
+        .map_err(|e| CrawlerError::Config(format!("acoustid base url invalid {e}")))?;
+
+    let rate_limit = env_rate_limit(
+        "ACOUSTID_MAX_RPS", "ACOUSTID_BURST", RateLimitConfig { max_rps: 3.0, burst: 3 }
+    );
+
+    Ok( AcoustIdConfig { base_url, api_key, rate_limit } )
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    pub max_attempts: u8,
+    pub base_backoff: time::Duration,
+    pub jitter: bool,
+    pub retryable_statuses: Vec<u16>,
+    /// Prefer a server-sent `Retry-After` over computed backoff when present.
+    pub respect_retry_after: bool,
+    /// Upper bound applied to a `Retry-After` value before sleeping on it.
+    pub retry_after_cap: time::Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: RETRY_MAX_ATTEMPTS,
+            base_backoff: time::Duration::from_millis(RETRY_BASE_BACKOFF),
+            jitter: RETRY_JITTER,
+            retryable_statuses: RETRYABLE_STATUSES.to_vec(),
+            respect_retry_after: true,
+            retry_after_cap: time::Duration::from_secs(RETRY_AFTER_CAP_SECS),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    pub timeout: time::Duration,
+    pub connect_timeout: time::Duration,
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout: time::Duration,
+    pub max_redirects: u8,
+    pub retry: RetryConfig,
+    pub default_max_rps: f32,
+    pub default_burst: u32,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout: time::Duration::from_millis(HTTP_TIMEOUT),
+            connect_timeout: time::Duration::from_millis(HTTP_CONNECT_TIMEOUT),
+            pool_max_idle_per_host: HTTP_POOL_MAX_IDLE,
+            pool_idle_timeout: time::Duration::from_millis(HTTP_POOL_IDLE_TIMEOUT),
+            max_redirects: HTTP_MAX_REDIRECTS,
+            retry: RetryConfig::default(),
+            default_max_rps: DEFAULT_MAX_RPS,
+            default_burst: DEFAULT_BURST,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat { Pretty, Json }
+
+#[derive(Debug, Clone)]
+pub struct LoggingConfig {
+    pub filter_directives: String,
+    pub format: LogFormat,
+    pub with_ansi: bool,
+    pub include_file_line: bool,
+    pub include_target: bool,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            filter_directives: "info,track_crawler=debug,reqwest=warn".to_string(),
+            format: LogFormat::Json,
+            with_ansi: true,
+            include_file_line: true,
+            include_target: true,
+        }
+    }
+}
+
+/// Governs the optional `/status` observability endpoint (see `status.rs`).
+/// Off by default so a crawl never binds a port unless asked to.
+#[derive(Debug, Clone)]
+pub struct StatusConfig {
+    pub enabled: bool,
+    pub bind_addr: std::net::SocketAddr,
+}
+
+impl Default for StatusConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_addr: "127.0.0.1:9300".parse().unwrap(),
+        }
+    }
+}
+
+fn build_status() -> StatusConfig {
+    let default = StatusConfig::default();
+    let enabled = std::env::var("STATUS_ENABLED")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(default.enabled);
+    let bind_addr = std::env::var("STATUS_BIND_ADDR")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(default.bind_addr);
+
+    StatusConfig { enabled, bind_addr }
+}
+
+/// Governs the optional Prometheus Pushgateway exporter (see `metrics.rs`).
+/// Off by default, like `StatusConfig`: this crate has no Cargo feature-flag
+/// mechanism (no manifest in this tree defines one), so the subsystem is
+/// gated the same way every other optional piece of this daemon is - a
+/// runtime config struct defaulting to disabled.
+#[derive(Debug, Clone)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub pushgateway_url: String,
+    pub push_interval: time::Duration,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pushgateway_url: "http://127.0.0.1:9091".to_string(),
+            push_interval: time::Duration::from_secs(15),
+        }
+    }
+}
+
+fn build_metrics() -> MetricsConfig {
+    let default = MetricsConfig::default();
+    let enabled = std::env::var("METRICS_ENABLED")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(default.enabled);
+    let pushgateway_url = std::env::var("METRICS_PUSHGATEWAY_URL")
+        .unwrap_or(default.pushgateway_url);
+    let push_interval = std::env::var("METRICS_PUSH_INTERVAL_SECS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(time::Duration::from_secs)
+        .unwrap_or(default.push_interval);
+
+    MetricsConfig { enabled, pushgateway_url, push_interval }
+}
+
+/// Governs resumable-crawl behavior (see `crawler::Crawler::run`'s startup
+/// reconciliation and `Crawler::seed_playlist`/`seed_album`'s seed cursor).
+/// Off by default: a fresh run behaves exactly as it did before this existed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResumeConfig {
+    pub enabled: bool,
+}
+
+fn build_resume() -> ResumeConfig {
+    let flag = std::env::args().any(|a| a == "--resume");
+    let env = std::env::var("RESUME")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    ResumeConfig { enabled: flag || env }
+}
+
+/// Governs `crawler::Crawler::feed_loop`'s discovery strategy: off (the
+/// default) samples random `year:{year}` + random-offset windows forever;
+/// on, it systematically pages every year bucket in 50-item chunks until
+/// each is exhausted, persisting a cursor so coverage survives a restart.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeedConfig {
+    pub exhaustive: bool,
+}
+
+fn build_feed() -> FeedConfig {
+    let flag = std::env::args().any(|a| a == "--exhaustive-feed");
+    let env = std::env::var("FEED_EXHAUSTIVE")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    FeedConfig { exhaustive: flag || env }
+}
+
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub identity: IdentityConfig,
+    pub spotify: SpotifyConfig,
+    pub musicbrainz: MusicBrainzConfig,
+    pub acousticbrainz: AcousticBrainzConfig,
+    pub lastfm: LastFmConfig,
+    pub acoustid: AcoustIdConfig,
+    pub http: HttpConfig,
+    pub logging: LoggingConfig,
+    pub status: StatusConfig,
+    pub resume: ResumeConfig,
+    pub metrics: MetricsConfig,
+    pub feed: FeedConfig,
+}
+
+pub fn load_config() -> Result<AppConfig, CrawlerError> {
+    dotenvy::dotenv().ok();
+
+    let identity      = build_identity()?;
+    let spotify        = build_spotify()?;
+    let musicbrainz    = build_musicbrainz()?;
+    let acousticbrainz = build_acousticbrainz()?;
+    let lastfm         = build_lastfm()?;
+    let acoustid       = build_acoustid()?;
+    let http           = HttpConfig::default();
+    let logging        = LoggingConfig::default();
+    let status         = build_status();
+    let resume         = build_resume();
+    let metrics        = build_metrics();
+    let feed           = build_feed();
+
+    Ok( AppConfig {
+        identity, spotify, musicbrainz, acousticbrainz, lastfm, acoustid, http, logging,
+        status, resume, metrics, feed
+    } )
+}
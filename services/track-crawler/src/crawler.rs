@@ -6,113 +6,81 @@
 //!
 //!
 
-use std::{sync::Arc, time::{Duration, Instant}};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use rand::{rngs::SmallRng, Rng, SeedableRng};
-use tokio::{sync::Semaphore, task::JoinHandle, time::sleep};
-use tokio_util::sync::CancellationToken; 
+use tokio::{sync::{Mutex, Semaphore}, task::JoinHandle, time::sleep};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use crate::{config::{self, HttpConfig, LoggingConfig}, fetch::LastFmClient, persistent};
-use crate::fetch::*;    // all clients are imported 
-use crate::persistent::{Job, JobType, Persistent, JobStatus};
+use crate::cache::{Cache, Lookup};
+use crate::{config::{HttpConfig, LoggingConfig, StatusConfig}, fetch::LastFmClient, persistent};
+use crate::fetch::*;    // all clients are imported
+use crate::ids::{Isrc, Mbid};
+use crate::metrics::{self, CrawlerMetrics, MetricsGauges};
+use crate::models;
+use crate::persistent::{Job, JobType, Persistent, JobStatus, SeedKind, LinkTier};
 use crate::sink::{DiskZstdSink, RawType};
-use crate::errors::CrawlerError;
-use crate::config::AppConfig; 
-
-#[derive(Debug)]
-struct RateGate {
-    min_interval: Duration, 
-    state: tokio::sync::Mutex<Instant> 
-}
-
-impl RateGate {
-    fn new(min_interval: Duration) -> Self {
-        Self { 
-            min_interval, 
-            state: tokio::sync::Mutex::new(Instant::now() - min_interval)
-        }
-    }
-    async fn wait(&self) {
-        let mut last = self.state.lock().await; 
-        let elapsed = last.elapsed();
-        if elapsed < self.min_interval { 
-            sleep(self.min_interval - elapsed).await; 
-        }
-        *last = Instant::now();
-    }
-}
-
-/// Simple function to generate random wait for http_with_retry
-fn generate_backoff(ms: u64, attempt: usize, rng: &mut SmallRng) -> Duration {
-    let exp = (1_u64 << attempt.min(6)) * ms; 
-    let jitter = rng.gen_range(50..=200) as u64; 
-    Duration::from_millis(exp + jitter)
-}
-
-async fn http_with_retry(
-    request: reqwest::RequestBuilder, 
-    max_retries: usize, 
-    backoff_ms: u64
-) -> Result<serde_json::Value, CrawlerError> {
-    let mut rng = SmallRng::from_entropy();
-    let mut attempt = 0_usize; 
-    loop {
-        let response = request.try_clone()
-            .ok_or_else(|| CrawlerError::Http("non-cloneable request".to_string()))?
-            .send()
-            .await;
-        match response {
-            Ok(resp) => {
-                if resp.status().is_success() {
-                    let v = resp.json::<serde_json::Value>().await?; 
-                    return Ok(v);
-                }
-                let status = resp.status(); 
-                let _body = resp.text().await.unwrap_or_default();
-                let retryable = status.as_u16() == 429 || status.is_server_error(); 
-                if !retryable || attempt >= max_retries {
-                    return Err(CrawlerError::Http(
-                        format!("status {} after {} retries", status, attempt)
-                    ));
-                }
-                let backoff = generate_backoff(backoff_ms, attempt, &mut rng);
-                warn!(status = %status, backoff = ?backoff.as_millis(), "http.retry");
-                sleep(backoff).await; 
-                attempt += 1;
-            },
-            Err(e) => {
-                if attempt >= max_retries {
-                    return Err(e.into());
-                }
-                let backoff = generate_backoff(backoff_ms, attempt, &mut rng);
-                warn!(backoff = ?backoff.as_millis(), "http.retry.error");
-                sleep(backoff).await; 
-                attempt += 1; 
-            }
-        }
-    }
-}
+use crate::errors::{CrawlerError, ErrorSeverity};
+use crate::config::{AppConfig, FeedConfig, MetricsConfig};
+use crate::status::{self, ClientsStats, JobOutcome, NotifyMap, PipelineCounters};
+use std::sync::atomic::Ordering;
 
 #[derive(Clone, Debug)]
 pub struct CrawlerLimits {
-    pub musicbrainz_limit: usize, 
-    pub musicbrainz_ms: u64,
-    pub feature_limit: usize, 
-    pub queue_poll_ms: u64, 
-    pub http_max_retry: usize, 
-    pub http_backoff_ms: u64
+    pub musicbrainz_limit: usize,
+    pub feature_limit: usize,
+    pub queue_poll_ms: u64,
+    /// Page size used when paging a playlist/album seed (Spotify caps this at 50).
+    pub seed_page_size: u32,
+    /// Stops a seed walk after this many track IDs, regardless of how much
+    /// more the playlist/album has left to page through.
+    pub seed_track_cap: usize,
+    /// How many times a transiently-failing job is requeued with backoff
+    /// before it's moved to `dead_letter` instead. A fatal error skips
+    /// straight to `dead_letter` regardless of this limit. Used only when
+    /// the job itself has no `Job::max_attempts` override.
+    pub job_max_attempts: i64,
+    /// How long `claim_one_job` holds a job `active` before
+    /// `reclaim_expired_jobs` considers the worker dead and puts it back to
+    /// `pending`. A worker doing something longer-lived than this (feature
+    /// extraction) must call `heartbeat_job` to extend its lease.
+    pub job_lease_secs: i64,
+    /// Max entries held by each lookup `cache::Cache` before the oldest is
+    /// evicted. One cache each for MBID resolution and AcousticBrainz/
+    /// Last.fm feature payloads.
+    pub cache_capacity: usize,
+    /// TTL for a cached hit - an ISRC/query/MBID that resolved to something.
+    pub cache_positive_ttl: Duration,
+    /// TTL for a cached "nothing found" - shorter, since a wrong negative
+    /// shouldn't go unnoticed for as long as a wrong positive would.
+    pub cache_missing_ttl: Duration,
+    /// Minimum `find_similar_tracks` Jaccard score for an already-linked
+    /// track to be reused as a dedup match in `resolve_mbid`, ahead of the
+    /// external text-search tier.
+    pub dedup_similarity_threshold: f64,
+    /// Max candidates `find_similar_tracks` returns to `resolve_mbid`'s
+    /// dedup tier.
+    pub dedup_candidate_limit: usize,
 }
 
 impl Default for CrawlerLimits {
     fn default() -> Self {
         Self {
-            musicbrainz_limit: 1, 
-            musicbrainz_ms: 1100,
-            feature_limit: 4, 
-            queue_poll_ms: 300, 
-            http_max_retry: 3, 
-            http_backoff_ms: 500 
+            musicbrainz_limit: 1,
+            feature_limit: 4,
+            queue_poll_ms: 300,
+            seed_page_size: 50,
+            seed_track_cap: 10_000,
+            job_max_attempts: 5,
+            job_lease_secs: 10 * 60,
+            cache_capacity: 4096,
+            cache_positive_ttl: Duration::from_secs(24 * 60 * 60),
+            cache_missing_ttl: Duration::from_secs(60 * 60),
+            dedup_similarity_threshold: 0.85,
+            dedup_candidate_limit: 5,
         }
     }
 }
@@ -120,72 +88,105 @@ impl Default for CrawlerLimits {
 #[derive(Clone)]
 pub struct Clients {
     pub spotify: Arc<SpotifyClient>,
-    pub musicbrainz: Arc<MusicBrainzClient>, 
-    pub acousticbrainz: Arc<AcousticBrainzClient>, 
-    pub lastfm: Arc<LastFmClient> 
+    pub musicbrainz: Arc<MusicBrainzClient>,
+    pub acousticbrainz: Arc<AcousticBrainzClient>,
+    pub lastfm: Arc<LastFmClient>,
+    pub acoustid: Arc<AcoustIdClient>
 }
 
 impl Clients {
     pub fn new(
-        spotify: SpotifyClient, 
-        musicbrainz: MusicBrainzClient, 
-        acousticbrainz: AcousticBrainzClient, 
-        lastfm: LastFmClient
+        spotify: SpotifyClient,
+        musicbrainz: MusicBrainzClient,
+        acousticbrainz: AcousticBrainzClient,
+        lastfm: LastFmClient,
+        acoustid: AcoustIdClient
     ) ->Self {
         Self {
             spotify: Arc::new(spotify),
             musicbrainz: Arc::new(musicbrainz),
             acousticbrainz: Arc::new(acousticbrainz),
-            lastfm: Arc::new(lastfm)
+            lastfm: Arc::new(lastfm),
+            acoustid: Arc::new(acoustid)
         }
     }
 }
 
 pub struct Crawler {
-    // backbone 
+    // backbone
     http: HttpConfig,
-    logging: LoggingConfig, 
-    limits: CrawlerLimits, 
-    db: Arc<Persistent>, 
-    clients: Clients, 
-    sink: Arc<DiskZstdSink>, 
-
-    // concurrency handlers 
-    musicbrainz_handler: Arc<Semaphore>, 
-    features_handler: Arc<Semaphore>, 
-    musicbrainz_rate: Arc<RateGate>,
-
-    // handles daemon exit 
+    logging: LoggingConfig,
+    status_cfg: StatusConfig,
+    metrics_cfg: MetricsConfig,
+    limits: CrawlerLimits,
+    /// Whether this run should reconcile stuck jobs and resume seed walks
+    /// from a saved cursor on startup (`--resume` / `RESUME`).
+    resume: bool,
+    /// Whether `feed_loop` sweeps year buckets exhaustively instead of
+    /// sampling random `year:{year}` + random-offset windows.
+    feed: FeedConfig,
+    db: Arc<Persistent>,
+    clients: Clients,
+    sink: Arc<DiskZstdSink>,
+    counters: Arc<PipelineCounters>,
+    metrics: Arc<CrawlerMetrics>,
+    /// One-shot result senders for in-flight `submit_foreground` calls, keyed
+    /// by track id. The job queue itself stays entirely in `persistent.rs`'s
+    /// sqlite tables (priority is a column on `jobs`, not an in-memory
+    /// deque); this map only carries the "tell me when it's done" half.
+    notify: NotifyMap,
+    /// Caches MBID resolutions (by ISRC and by title/artist text query) so
+    /// the same duplicate track surfacing in a later feed window doesn't
+    /// re-burn the MusicBrainz rate-limit budget.
+    mbid_cache: Arc<Cache<String>>,
+    /// Caches AcousticBrainz high/low-level payloads and Last.fm tags,
+    /// keyed by MBID (or artist/title for the Last.fm text fallback).
+    feature_cache: Arc<Cache<serde_json::Value>>,
+
+    // concurrency handlers
+    musicbrainz_handler: Arc<Semaphore>,
+    features_handler: Arc<Semaphore>,
+
+    // handles daemon exit
     shutdown: CancellationToken
 }
 
 impl Crawler {
     pub fn new(
-        cfg: &AppConfig, 
-        db: Persistent, 
-        clients: Clients, 
+        cfg: &AppConfig,
+        db: Persistent,
+        clients: Clients,
         sink: DiskZstdSink,
-        limits: CrawlerLimits 
+        limits: CrawlerLimits
     ) -> Self {
        let musicbrainz_handler = Arc::new(Semaphore::new(limits.musicbrainz_limit));
        let features_handler    = Arc::new(Semaphore::new(limits.feature_limit));
-       let musicbrainz_rate    = Arc::new(RateGate::new(
-           Duration::from_millis(limits.musicbrainz_ms)
-       ));
 
        Self {
            http: cfg.http.clone(),
            logging: cfg.logging.clone(),
-           limits, 
+           status_cfg: cfg.status.clone(),
+           metrics_cfg: cfg.metrics.clone(),
+           limits,
+           resume: cfg.resume.enabled,
+           feed: cfg.feed,
            db: Arc::new(db),
-           clients, 
+           clients,
            sink: Arc::new(sink),
-           musicbrainz_handler, 
-           features_handler, 
-           musicbrainz_rate,
+           counters: Arc::new(PipelineCounters::new()),
+           metrics: Arc::new(CrawlerMetrics::new()),
+           notify: Arc::new(Mutex::new(HashMap::new())),
+           mbid_cache: Arc::new(Cache::new(
+               limits.cache_capacity, limits.cache_positive_ttl, limits.cache_missing_ttl
+           )),
+           feature_cache: Arc::new(Cache::new(
+               limits.cache_capacity, limits.cache_positive_ttl, limits.cache_missing_ttl
+           )),
+           musicbrainz_handler,
+           features_handler,
            shutdown: CancellationToken::new()
        }
-    } 
+    }
 
     pub fn shutdown(&self) -> CancellationToken {
         self.shutdown.clone()
@@ -198,9 +199,21 @@ impl Crawler {
             "crawler.start",
         );
 
-        let link_handle = self.spawn_link_workers(); 
-        let feat_handle = self.spawn_feature_workers(); 
-        let feed_handle = self.spawn_feed_worker(); 
+        if self.resume {
+            self.reconcile_resume().await;
+        }
+
+        let link_handle = self.spawn_link_workers();
+        let feat_handle = self.spawn_feature_workers();
+        let feed_handle = self.spawn_feed_worker();
+        self.spawn_lease_reclaimer();
+
+        if self.status_cfg.enabled {
+            self.spawn_status_server();
+        }
+        if self.metrics_cfg.enabled {
+            self.spawn_metrics_pusher();
+        }
 
         let shutdown = self.shutdown.clone();
         let trigger = tokio::spawn(async move {
@@ -249,22 +262,171 @@ impl Crawler {
     }
 
     fn spawn_feature_workers(&self) -> JoinHandle<()> {
-        let this = self.clone_for_task(); 
+        let this = self.clone_for_task();
         tokio::spawn(async move { this.features_loop().await })
     }
 
+    /// Spawns the `/status` observability server; not part of the critical
+    /// select! loop in `run`, since a failure here shouldn't tear down a crawl.
+    fn spawn_status_server(&self) -> JoinHandle<()> {
+        let addr = self.status_cfg.bind_addr;
+        let counters = self.counters.clone();
+        let clients_stats = ClientsStats {
+            spotify: self.clients.spotify.stats.clone(),
+            musicbrainz: self.clients.musicbrainz.stats.clone(),
+            acousticbrainz: self.clients.acousticbrainz.stats.clone(),
+            lastfm: self.clients.lastfm.stats.clone(),
+            acoustid: self.clients.acoustid.stats.clone(),
+        };
+        let caches_stats = status::CachesStats {
+            mbid: self.mbid_cache.clone(),
+            feature: self.feature_cache.clone(),
+        };
+        let notify = self.notify.clone();
+        let db = self.db.clone();
+        let shutdown = self.shutdown.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = status::serve(
+                addr, counters, clients_stats, caches_stats, notify, db, shutdown
+            ).await {
+                error!(error = ?e, "status.serve failed");
+            }
+        })
+    }
+
+    /// Spawns the Prometheus Pushgateway exporter; like `spawn_status_server`,
+    /// a failure here shouldn't tear down a crawl.
+    fn spawn_metrics_pusher(&self) -> JoinHandle<()> {
+        let cfg = self.metrics_cfg.clone();
+        let metrics = self.metrics.clone();
+        let clients = ClientsStats {
+            spotify: self.clients.spotify.stats.clone(),
+            musicbrainz: self.clients.musicbrainz.stats.clone(),
+            acousticbrainz: self.clients.acousticbrainz.stats.clone(),
+            lastfm: self.clients.lastfm.stats.clone(),
+            acoustid: self.clients.acoustid.stats.clone(),
+        };
+        let gauges = MetricsGauges {
+            musicbrainz_handler: self.musicbrainz_handler.clone(),
+            musicbrainz_limit: self.limits.musicbrainz_limit,
+            features_handler: self.features_handler.clone(),
+            feature_limit: self.limits.feature_limit,
+        };
+        let counters = self.counters.clone();
+        let db = self.db.clone();
+        let shutdown = self.shutdown.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = metrics::push_loop(cfg, metrics, clients, gauges, counters, db, shutdown).await {
+                error!(error = ?e, "metrics.push_loop failed");
+            }
+        })
+    }
+
+    /// Spawns the lease reclaimer; like `spawn_status_server`, not part of
+    /// the critical `select!` in `run` since a failure here shouldn't tear
+    /// down a crawl - a stuck job just stays stuck a bit longer.
+    fn spawn_lease_reclaimer(&self) -> JoinHandle<()> {
+        let this = self.clone_for_task();
+        tokio::spawn(async move { this.reclaim_loop().await })
+    }
+
+    /// Periodically puts `active` jobs whose lease has lapsed back to
+    /// `pending`, so a worker that crashed or hung mid-job doesn't strand it
+    /// until the next `--resume` restart.
+    async fn reclaim_loop(&self) {
+        let period = Duration::from_secs(self.limits.job_lease_secs as u64);
+        while !self.shutdown.is_cancelled() {
+            sleep(period).await;
+            for kind in [JobType::Link, JobType::Features] {
+                match self.db.reclaim_expired_jobs(kind, self.limits.job_lease_secs).await {
+                    Ok(0) => {}
+                    Ok(n) => warn!(kind = kind.as_str(), reclaimed = n, "jobs.lease_reclaimed"),
+                    Err(e) => error!(error = ?e, kind = kind.as_str(), "reclaim_expired_jobs failed"),
+                }
+            }
+        }
+    }
+
+    /// Runs once on startup when `--resume` is set: un-sticks jobs a prior
+    /// process left `active` when it was killed, and re-enqueues a link or
+    /// features job for any track the DB shows as incomplete but that has no
+    /// job backing it (e.g. the job row itself was lost before a crash).
+    async fn reconcile_resume(&self) {
+        match self.db.reset_stuck_jobs().await {
+            Ok(0) => {}
+            Ok(n) => info!(reset = n, "resume.reset_stuck_jobs"),
+            Err(e) => warn!(error = ?e, "resume.reset_stuck_jobs failed"),
+        }
+
+        match self.db.requeue_incomplete_tracks().await {
+            Ok((0, 0)) => {}
+            Ok((link, features)) => info!(link, features, "resume.requeue_incomplete_tracks"),
+            Err(e) => warn!(error = ?e, "resume.requeue_incomplete_tracks failed"),
+        }
+    }
+
+    /// Routes a failed job to either a backoff-and-retry (transient, under
+    /// `job_max_attempts`) or `dead_letter` (fatal, or transient but out of
+    /// attempts) instead of leaving it stuck `active` forever.
+    async fn handle_job_failure(&self, job: &Job, err: CrawlerError) {
+        let message = err.to_string();
+        let fatal = err.severity() == ErrorSeverity::Fatal;
+
+        let max_attempts = job.max_attempts.unwrap_or(self.limits.job_max_attempts);
+        if fatal || job.attempt >= max_attempts {
+            warn!(job_id = job.job_id, track = %job.track_id, attempt = job.attempt,
+                fatal, error = %message, "job.dead_letter");
+            if let Err(e) = self.db.dead_letter_job(job, &message).await {
+                error!(error = ?e, "dead_letter_job failed");
+            }
+            let outcome = match job.kind {
+                JobType::Link => JobOutcome::Failed(message),
+                JobType::Features => JobOutcome::Partial(message),
+            };
+            self.notify_outcome(&job.track_id, outcome).await;
+            return;
+        }
+
+        let delay = generate_backoff(Duration::from_millis(500), job.attempt as u32, true);
+        self.metrics.backoff.observe(delay);
+        warn!(job_id = job.job_id, track = %job.track_id, attempt = job.attempt,
+            delay_ms = delay.as_millis(), error = %message, "job.requeue");
+        if let Err(e) = self.db.requeue_job(job.job_id, delay).await {
+            error!(error = ?e, "requeue_job failed");
+        }
+    }
+
+    /// Fires and removes any `status::submit_foreground` receiver waiting on
+    /// `track_id`, called when a job is dead-lettered or a features job
+    /// completes.
+    async fn notify_outcome(&self, track_id: &str, outcome: JobOutcome) {
+        if let Some(tx) = self.notify.lock().await.remove(track_id) {
+            let _ = tx.send(outcome);
+        }
+    }
+
     fn clone_for_task(&self) -> Self {
         Self {
-            http: self.http.clone(), 
-            logging: self.logging.clone(), 
-            limits: self.limits.clone(), 
-            db: self.db.clone(), 
-            clients: self.clients.clone(), 
-            sink: self.sink.clone(), 
+            http: self.http.clone(),
+            logging: self.logging.clone(),
+            status_cfg: self.status_cfg.clone(),
+            metrics_cfg: self.metrics_cfg.clone(),
+            limits: self.limits.clone(),
+            resume: self.resume,
+            feed: self.feed,
+            db: self.db.clone(),
+            clients: self.clients.clone(),
+            sink: self.sink.clone(),
+            counters: self.counters.clone(),
+            metrics: self.metrics.clone(),
+            notify: self.notify.clone(),
+            mbid_cache: self.mbid_cache.clone(),
+            feature_cache: self.feature_cache.clone(),
             musicbrainz_handler: self.musicbrainz_handler.clone(),
             features_handler: self.features_handler.clone(),
-            musicbrainz_rate: self.musicbrainz_rate.clone(), 
-            shutdown: self.shutdown.clone(), 
+            shutdown: self.shutdown.clone(),
         }
     }
 
@@ -272,147 +434,278 @@ impl Crawler {
         info!("crawler.link.loop.start");
         let poll = Duration::from_millis(self.limits.queue_poll_ms);
         while !self.shutdown.is_cancelled() {
-            self.musicbrainz_rate.wait().await;
-
-            match self.db.claim_one_job(JobType::Link).await {
+            match self.db.claim_one_job(JobType::Link, self.limits.job_lease_secs).await {
                 Ok(Some(job)) => {
                     let _permit = match self.musicbrainz_handler.acquire().await {
-                        Ok(p) => p, 
+                        Ok(p) => p,
                         Err(_) => break
                     };
-                    if let Err(e) = self.process_link_job(job).await {
-                        error!(error = ?e, "link job failed");
+                    self.metrics.link.claimed.fetch_add(1, Ordering::Relaxed);
+                    let start = std::time::Instant::now();
+                    let result = self.process_link_job(&job).await;
+                    self.metrics.link.latency.observe(start.elapsed());
+                    match result {
+                        Ok(()) => { self.metrics.link.completed.fetch_add(1, Ordering::Relaxed); }
+                        Err(e) => {
+                            self.metrics.link.failed.fetch_add(1, Ordering::Relaxed);
+                            self.handle_job_failure(&job, e).await;
+                        }
                     }
                 }
-                Ok(None) => { 
-                    sleep(poll).await; 
+                Ok(None) => {
+                    sleep(poll).await;
                 }
                 Err(e) => {
                     error!(error = ?e, "claim_one_job(Link) failed");
-                    sleep(poll).await; 
+                    sleep(poll).await;
                 }
             }
         }
         info!("crawler.link.loop.stop");
     }
 
-    async fn process_link_job(&self, job: Job) -> Result<(), CrawlerError> {
+    async fn process_link_job(&self, job: &Job) -> Result<(), CrawlerError> {
         debug!(
-            job_id = job.job_id, track = %job.track_id, 
+            job_id = job.job_id, track = %job.track_id,
             attempt = job.attempt, "link.process");
 
-        let meta = match self.db.get_track_metadata(&job.track_id).await? {
-            Some(m) => m,
-            None => {
-                self.db.fail_job(job.job_id, "track not found").await?; 
-                info!(job_id = job.job_id, track = %job.track_id, "link.skip.no_track");
-                return Ok(())
+        let meta = self.db.get_track_metadata(&job.track_id).await?
+            .ok_or_else(|| CrawlerError::NotFound("track not found in db".into()))?;
+
+        // Already resolved (e.g. a resumed run re-enqueued this job before
+        // the job row caught up to the track's own `linked_ok` state) -
+        // don't hit MusicBrainz again for nothing.
+        if let Some(mbid) = meta.mb_recording_id.clone() {
+            self.db.complete_job(job.job_id).await?;
+            if let Err(e) = self.db.enqueue_features(&job.track_id, job.priority).await {
+                warn!(error = ?e, "enqueue_features");
             }
-        };
+            info!(job_id = job.job_id, track = %job.track_id, mbid = %mbid, "link.already_resolved");
+            return Ok(());
+        }
 
-        let mbid = if let Some(isrc) = meta.isrc.as_deref() {
-            self.lookup_mbid_by_isrc(isrc).await? 
-        } else {
-            let title  = meta.title.as_deref().unwrap_or("");
-            let artist = meta.first_artist();
-            self.lookup_mbid_by_query(title, artist).await?
-        };
+        let (mbid, tier) = self.resolve_mbid(&meta).await?;
 
-        self.db.set_mbid(&job.track_id, &mbid).await?; 
+        self.db.set_mbid(&job.track_id, &mbid, tier).await?;
+        self.counters.linked_ok.fetch_add(1, Ordering::Relaxed);
         self.db.complete_job(job.job_id).await?;
-        
-        if let Err(e) = self.db.enqueue_features(&job.track_id).await {
+
+        if let Err(e) = self.db.enqueue_features(&job.track_id, job.priority).await {
             warn!(error = ?e, "enqueue_features");
         }
-        info!(job_id = job.job_id, track = %job.track_id, mbid = %mbid, "link.done");
+        info!(job_id = job.job_id, track = %job.track_id, mbid = %mbid, tier = tier.as_str(), "link.done");
         Ok(())
     }
 
-    async fn lookup_mbid_by_isrc(&self, isrc: &str) -> Result<String, CrawlerError> {
-        let resp = self.clients.musicbrainz.lookup_isrc(isrc);
-        let value = http_with_retry(
-            resp, self.limits.http_max_retry,
-            self.limits.http_backoff_ms
+    /// MBID resolution cascade: an ISRC lookup first (cheapest, most
+    /// precise), then a local trigram dedup match against already-linked
+    /// tracks, then a fuzzy title/artist text search against MusicBrainz,
+    /// then an AcoustID fingerprint lookup as a last resort for the live
+    /// versions, remasters, and mistagged tracks the rest all miss. Each
+    /// tier only runs if the previous one came back empty.
+    async fn resolve_mbid(&self, meta: &persistent::Track) -> Result<(String, LinkTier), CrawlerError> {
+        if let Some(isrc) = meta.isrc.as_deref() {
+            match self.lookup_mbid_by_isrc(isrc).await {
+                Ok(mbid) => return Ok((mbid, LinkTier::Isrc)),
+                // Genuinely no recording for this ISRC - fall through to the
+                // next tier. Anything else (rate-limited, transport, db) is
+                // transient/fatal per `severity()` and must propagate so
+                // `handle_job_failure` can retry instead of dead-lettering.
+                Err(CrawlerError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let title = meta.title.as_deref().unwrap_or("");
+        let artist = meta.first_artist();
+
+        // Before paying for an external text search, check whether a
+        // near-duplicate track (different Spotify ID, same recording -
+        // e.g. a reissue or regional release) is already linked in our own
+        // DB; if so, reuse its MBID instead of re-resolving it.
+        if let Some(mbid) = self.find_linked_duplicate(&meta.id, title, artist).await? {
+            return Ok((mbid, LinkTier::Text));
+        }
+
+        match self.lookup_mbid_by_query(title, artist).await {
+            Ok(mbid) => return Ok((mbid, LinkTier::Text)),
+            Err(CrawlerError::NotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        // Spotify's metadata API never hands back a Chromaprint fingerprint,
+        // so `meta.fingerprint` is always `None` out of the current ingest
+        // path - this tier only fires once something upstream starts
+        // populating it.
+        if let Some(fingerprint) = meta.fingerprint.as_deref() {
+            let duration_secs = (meta.duration_ms.unwrap_or(0) / 1000) as u32;
+            return self.lookup_mbid_by_fingerprint(fingerprint, duration_secs).await
+                .map(|mbid| (mbid, LinkTier::AcoustId));
+        }
+
+        Err(CrawlerError::NotFound("no recording for isrc, text, or fingerprint".into()))
+    }
+
+    /// Looks for an already-linked track whose title/artist trigram set is
+    /// at least `dedup_similarity_threshold` similar to `title`/`artist`,
+    /// skipping `self_id` so a track never matches itself. Returns the
+    /// highest-scoring match's MBID, if any candidate is both similar enough
+    /// and actually linked.
+    async fn find_linked_duplicate(&self, self_id: &str, title: &str, artist: &str) ->
+        Result<Option<String>, CrawlerError> {
+        let candidates = self.db.find_similar_tracks(
+            title, artist,
+            self.limits.dedup_similarity_threshold,
+            self.limits.dedup_candidate_limit,
         ).await?;
-        let records = value["recordings"].as_array().unwrap();
-        let mbid = records.iter() 
-            .filter_map(|r| r.get("id").and_then(|x| x.as_str()))
-            .next()
-            .ok_or_else(|| CrawlerError::Http("no recording for ISRC".into()))?
-            .to_string();
-        Ok(mbid)
+
+        for (track_id, _score) in candidates {
+            if track_id == self_id {
+                continue;
+            }
+            if let Some(candidate) = self.db.get_track_metadata(&track_id).await? {
+                if candidate.linked_ok {
+                    if let Some(mbid) = candidate.mb_recording_id {
+                        return Ok(Some(mbid));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn lookup_mbid_by_isrc(&self, isrc: &str) -> Result<String, CrawlerError> {
+        let key = format!("isrc:{isrc}");
+        match self.mbid_cache.get(&key).await {
+            Lookup::Found(mbid) => return Ok(mbid),
+            Lookup::Missing => return Err(CrawlerError::NotFound("no recording for ISRC (cached)".into())),
+            Lookup::Absent => {}
+        }
+
+        let isrc_typed = Isrc::from_str(isrc)?;
+        let lookup = self.clients.musicbrainz.lookup_isrc(&isrc_typed).await?;
+        match lookup.recordings.into_iter().next().map(|r| r.id) {
+            Some(mbid) => {
+                self.mbid_cache.put_found(key, mbid.clone()).await;
+                Ok(mbid)
+            }
+            None => {
+                self.mbid_cache.put_missing(key).await;
+                Err(CrawlerError::NotFound("no recording for ISRC".into()))
+            }
+        }
     }
 
-    async fn lookup_mbid_by_query(&self, title: &str, artist: &str) -> 
+    async fn lookup_mbid_by_query(&self, title: &str, artist: &str) ->
         Result<String, CrawlerError> {
+        let key = format!("query:{title}|{artist}");
+        match self.mbid_cache.get(&key).await {
+            Lookup::Found(mbid) => return Ok(mbid),
+            Lookup::Missing => return Err(CrawlerError::NotFound("no recording for query (cached)".into())),
+            Lookup::Absent => {}
+        }
+
         let query = format!("recording:\"{}\" AND artist:\"{}\"", title, artist);
-        let resp = self.clients.musicbrainz.search_recording(&query, 10, 0);
-        let value = http_with_retry(
-            resp, self.limits.http_max_retry,
-            self.limits.http_backoff_ms
-        ).await?;
-        let records = value["recordings"].as_array().unwrap();
-        let mbid = records.iter() 
-            .filter_map(|r| r.get("id").and_then(|x| x.as_str()))
-            .next()
-            .ok_or_else(|| CrawlerError::Http("no recording for ISRC".into()))?
-            .to_string();
-        Ok(mbid)
+        let lookup = self.clients.musicbrainz.search_recording(&query, 10, 0).await?;
+        match lookup.recordings.into_iter().next().map(|r| r.id) {
+            Some(mbid) => {
+                self.mbid_cache.put_found(key, mbid.clone()).await;
+                Ok(mbid)
+            }
+            None => {
+                self.mbid_cache.put_missing(key).await;
+                Err(CrawlerError::NotFound("no recording for query".into()))
+            }
+        }
+    }
+
+    async fn lookup_mbid_by_fingerprint(&self, fingerprint: &str, duration_secs: u32) ->
+        Result<String, CrawlerError> {
+        let lookup = self.clients.acoustid.lookup_fingerprint(fingerprint, duration_secs).await?;
+        lookup.results.into_iter()
+            .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(std::cmp::Ordering::Equal))
+            .and_then(|r| r.recordings.into_iter().next())
+            .map(|r| r.id)
+            .ok_or_else(|| CrawlerError::NotFound("no recording for fingerprint".into()))
     }
 
     async fn features_loop(&self) {
         info!("crawler.features.loop.start");
         let poll = Duration::from_millis(self.limits.queue_poll_ms);
         while !self.shutdown.is_cancelled() {
-            match self.db.claim_one_job(JobType::Features).await {
+            match self.db.claim_one_job(JobType::Features, self.limits.job_lease_secs).await {
                 Ok(Some(job)) => {
                     let _permit = match self.features_handler.acquire().await {
-                        Ok(p) => p, 
+                        Ok(p) => p,
                         Err(_) => break
                     };
-                    if let Err(e) = self.process_features_job(job).await {
-                        error!(error = ?e, "features job failed");
+                    self.metrics.features.claimed.fetch_add(1, Ordering::Relaxed);
+                    let start = std::time::Instant::now();
+                    let result = self.process_features_job(&job).await;
+                    self.metrics.features.latency.observe(start.elapsed());
+                    match result {
+                        Ok(()) => { self.metrics.features.completed.fetch_add(1, Ordering::Relaxed); }
+                        Err(e) => {
+                            self.metrics.features.failed.fetch_add(1, Ordering::Relaxed);
+                            self.handle_job_failure(&job, e).await;
+                        }
                     }
                 }
-                Ok(None) => { 
-                    sleep(poll).await; 
+                Ok(None) => {
+                    sleep(poll).await;
                 }
                 Err(e) => {
                     error!(error = ?e, "claim_one_job(Features) failed");
-                    sleep(poll).await; 
+                    sleep(poll).await;
                 }
             }
         }
         info!("crawler.features.loop.stop");
     }
 
-    async fn process_features_job(&self, job: Job) -> Result<(), CrawlerError> {
-        debug!(job_id = job.job_id, track = %job.track_id, attempt = job.attempt, 
+    async fn process_features_job(&self, job: &Job) -> Result<(), CrawlerError> {
+        debug!(job_id = job.job_id, track = %job.track_id, attempt = job.attempt,
             "features.process");
 
-        let meta = match self.db.get_track_metadata(&job.track_id).await 
+        let meta = self.db.get_track_metadata(&job.track_id).await
             .map_err(|e| CrawlerError::Db(format!("get_track_metadata: {e}")))?
-        {
-            Some(m) => m, 
-            None => {
-                self.db.fail_job(job.job_id, "track not found").await?; 
-                info!(job_id = job.job_id, track = %job.track_id, "skip.no_track");
-                return Ok(());
-            }
-        };
+            .ok_or_else(|| CrawlerError::NotFound("track not found in db".into()))?;
+
+        // A resumed run can re-enqueue a features job for a track that's
+        // already done; skip straight to completing it instead of re-hitting
+        // AcousticBrainz/Last.fm.
+        if meta.features_ok {
+            self.db.complete_job(job.job_id).await?;
+            info!(job_id = job.job_id, track = %job.track_id, "features.already_done");
+            return Ok(());
+        }
 
         let mbid = meta.mb_recording_id
             .as_deref()
             .ok_or_else(|| CrawlerError::NotFound("No mbid found".into()))?;
-        let highlevel = http_with_retry(
-            self.clients.acousticbrainz.features(mbid, "high-level"), 
-            self.limits.http_max_retry, 
-            self.limits.http_backoff_ms
-        ).await?;
+        let mbid_typed = Mbid::from_str(mbid)?;
+
+        let hl_key = format!("ab_high:{mbid}");
+        let highlevel = match self.feature_cache.get(&hl_key).await {
+            Lookup::Found(v) => v,
+            Lookup::Missing => return Err(CrawlerError::NotFound("no acousticbrainz high-level (cached)".into())),
+            Lookup::Absent => match self.clients.acousticbrainz.high_level(&mbid_typed).await {
+                Ok(hl) => {
+                    self.feature_cache.put_found(hl_key, hl.0.clone()).await;
+                    hl.0
+                }
+                Err(e) if e.severity() == ErrorSeverity::Fatal => {
+                    self.feature_cache.put_missing(hl_key).await;
+                    return Err(e);
+                }
+                Err(e) => return Err(e),
+            },
+        };
 
         let path_highlevel = self.sink.write_json(
-            RawType::ABHighLevel, 
-            mbid, 
+            RawType::ABHighLevel,
+            mbid,
             highlevel.clone()
         )?;
         self.db.index_raw_file(
@@ -432,12 +725,29 @@ impl Crawler {
         self.db.upsert_features_text(&job.track_id, "acousticbrainz", &highlevel_text)
             .await?; 
 
-        let lowlevel = self.clients.acousticbrainz.features(mbid, "low-level");
-        let lowlevel = http_with_retry(
-            lowlevel, 
-            self.limits.http_max_retry, 
-            self.limits.http_backoff_ms
-        ).await?;
+        // This job is a chain of several network calls; extend the lease
+        // claim_one_job set so reclaim_expired_jobs doesn't mistake this
+        // still-running job for a dead worker's.
+        if let Err(e) = self.db.heartbeat_job(job.job_id, self.limits.job_lease_secs).await {
+            warn!(error = ?e, job_id = job.job_id, "heartbeat_job failed");
+        }
+
+        let ll_key = format!("ab_low:{mbid}");
+        let lowlevel = match self.feature_cache.get(&ll_key).await {
+            Lookup::Found(v) => v,
+            Lookup::Missing => return Err(CrawlerError::NotFound("no acousticbrainz low-level (cached)".into())),
+            Lookup::Absent => match self.clients.acousticbrainz.low_level(&mbid_typed).await {
+                Ok(ll) => {
+                    self.feature_cache.put_found(ll_key, ll.0.clone()).await;
+                    ll.0
+                }
+                Err(e) if e.severity() == ErrorSeverity::Fatal => {
+                    self.feature_cache.put_missing(ll_key).await;
+                    return Err(e);
+                }
+                Err(e) => return Err(e),
+            },
+        };
 
         let path_lowlevel = self.sink.write_json(
             RawType::ABLowLevel, 
@@ -457,21 +767,43 @@ impl Crawler {
         self.db.upsert_features_num(&job.track_id, "acousticbrainz", &lowlevel_numeric)
             .await?; 
 
-        // Get tags from mbid, if fails get conventionally else warning 
-        let mut tags = {
-            let resp = self.clients.lastfm.track_top_tags_by_mbid(mbid);
-            http_with_retry(resp, self.limits.http_max_retry, self.limits.http_backoff_ms)
-                .await 
+        // Get tags from mbid, if fails get conventionally else warning
+        let mbid_tag_key = format!("lastfm_mbid:{mbid}");
+        let mut tags: Result<serde_json::Value, CrawlerError> = match self.feature_cache.get(&mbid_tag_key).await {
+            Lookup::Found(v) => Ok(v),
+            Lookup::Missing => Err(CrawlerError::NotFound("no lastfm tags for mbid (cached)".into())),
+            Lookup::Absent => match self.clients.lastfm.track_top_tags_by_mbid(mbid).await {
+                Ok(t) => {
+                    self.feature_cache.put_found(mbid_tag_key, t.0.clone()).await;
+                    Ok(t.0)
+                }
+                Err(e) if e.severity() == ErrorSeverity::Fatal => {
+                    self.feature_cache.put_missing(mbid_tag_key).await;
+                    Err(e)
+                }
+                Err(e) => Err(e),
+            },
         };
-        
+
         if tags.is_err() {
             let title  = meta.title.as_deref().unwrap_or("");
-            let artist = meta.first_artist(); 
-            tags = http_with_retry(
-                self.clients.lastfm.track_top_tags(&artist, &title),
-                self.limits.http_max_retry, 
-                self.limits.http_backoff_ms
-            ).await;
+            let artist = meta.first_artist();
+            let query_tag_key = format!("lastfm_query:{artist}|{title}");
+            tags = match self.feature_cache.get(&query_tag_key).await {
+                Lookup::Found(v) => Ok(v),
+                Lookup::Missing => Err(CrawlerError::NotFound("no lastfm tags for query (cached)".into())),
+                Lookup::Absent => match self.clients.lastfm.track_top_tags(artist, title).await {
+                    Ok(t) => {
+                        self.feature_cache.put_found(query_tag_key, t.0.clone()).await;
+                        Ok(t.0)
+                    }
+                    Err(e) if e.severity() == ErrorSeverity::Fatal => {
+                        self.feature_cache.put_missing(query_tag_key).await;
+                        Err(e)
+                    }
+                    Err(e) => Err(e),
+                },
+            };
         }
 
         if let Ok(tags) = tags {
@@ -495,85 +827,37 @@ impl Crawler {
             warn!(track = %job.track_id, "lastfm tags missing");
         }
 
-        self.db.mark_features_ok(&job.track_id).await?; 
-        self.db.complete_job(job.job_id).await?; 
+        self.db.mark_features_ok(&job.track_id).await?;
+        self.counters.features_ok.fetch_add(1, Ordering::Relaxed);
+        self.db.complete_job(job.job_id).await?;
+        self.notify_outcome(&job.track_id, JobOutcome::Complete).await;
         info!(job_id = job.job_id, track = %job.track_id, "features.done");
 
         Ok(())
     }
 
-    async fn refresh_token(
-        client: &SpotifyClient, 
-        cfg: &config::SpotifyConfig, 
-        max_retry: usize,
-        backoff_ms: u64
-    ) -> Result<(String, tokio::time::Instant), CrawlerError> {
-        let response = http_with_retry(
-            client.token_request().basic_auth(
-                &cfg.client_id, 
-                Some(&cfg.client_secret)
-            ), 
-            max_retry, 
-            backoff_ms
-        ).await?; 
-        let token_str = response["access_token"].as_str() 
-            .ok_or_else(|| CrawlerError::Http("no access_token in response".into()))?
-            .to_string();
-        let expires_in = response["expires_in"].as_u64().unwrap_or(3600);
-        let expire_time = tokio::time::Instant::now() + std::time::Duration::from_secs(expires_in - 60);
-        Ok((token_str, expire_time))
-    }
-
-    async fn insert_tracks(&self, search: serde_json::Value, token: &str) -> bool{
-        let items = search.pointer("/tracks/items")
-            .and_then(|v| v.as_array())
-            .cloned()
-            .unwrap_or_default();
-        if items.is_empty() {
-            debug!("no tracks found for query");
-            return false; 
-        } 
-        
-        let ids: Vec<&str> = items.iter()
-            .filter_map(|i| i.get("id").and_then(|v| v.as_str()))
-            .collect();
-        let ids = ids.join(",");
-        
-        let tracks = http_with_retry(
-            self.clients.spotify.batch_track(
-                &ids, 
-                token
-            ),
-            self.limits.http_max_retry,
-            self.limits.http_backoff_ms
-        ).await; 
-
-        let tracks = match tracks {
-            Ok(value) => value, 
-            Err(e) => {
-                warn!(error = ?e, "spotify batch request failed");
-                sleep(Duration::from_millis(self.limits.queue_poll_ms))
-                    .await;
-                return false; 
-            }
-        };
+    async fn refresh_token(client: &SpotifyClient) ->
+        Result<(String, tokio::time::Instant), CrawlerError> {
+        let token = client.request_token().await?;
+        let expire_time = tokio::time::Instant::now()
+            + std::time::Duration::from_secs(token.expires_in.saturating_sub(60));
+        Ok((token.access_token, expire_time))
+    }
 
-        let tracks = tracks.get("tracks")
-            .and_then(|v| v.as_array())
-            .cloned()
-            .unwrap_or_default();
-        let mut count = 0; 
-        for track in tracks {
-            if track.is_null() {
-                continue; 
-            }
+    /// Upserts each track into the DB and writes its raw JSON to the sink,
+    /// enqueueing a link job for anything new. Returns how many were added.
+    async fn ingest_tracks(&self, tracks: Vec<models::SpotifyTrack>) -> usize {
+        self.counters.tracks_discovered.fetch_add(tracks.len() as u64, Ordering::Relaxed);
 
+        let mut count = 0;
+        for track in tracks {
             let spotify_track = persistent::SpotifyTrack::new(&track);
             match self.db.ensure_track(&spotify_track).await {
                 Ok(track_id) => {
-                    count += 1; 
+                    count += 1;
+                    self.counters.tracks_upserted.fetch_add(1, Ordering::Relaxed);
                     debug!(
-                        track = %track_id, 
+                        track = %track_id,
                         title = %spotify_track.title,
                         "track ensured in db"
                     );
@@ -585,20 +869,21 @@ impl Crawler {
             }
 
             if let Some(spotify_id) = spotify_track.spotify_id.as_deref() {
+                let raw = serde_json::to_value(&track).unwrap_or(serde_json::Value::Null);
                 match self.sink.write_json(
-                    RawType::SpotifyTrack, 
-                    spotify_id, 
-                    track.clone()) {
+                    RawType::SpotifyTrack,
+                    spotify_id,
+                    raw) {
 
                     Ok(path) => {
                         if let Err(e) = self.db.index_raw_file(
                             spotify_id,
                             "spotify",
-                            "track", 
+                            "track",
                             spotify_id,
                             path.to_str().unwrap_or_default()
                         ).await {
-                            warn!(error = ?e, spotify_id = %spotify_id, 
+                            warn!(error = ?e, spotify_id = %spotify_id,
                                 "index_raw_file spotify");
                         }
                     }
@@ -608,11 +893,188 @@ impl Crawler {
                 }
             }
         }
+        count
+    }
+
+    async fn insert_tracks(&self, search: models::SpotifySearchResponse, token: &str) -> bool {
+        if search.tracks.items.is_empty() {
+            debug!("no tracks found for query");
+            return false;
+        }
+
+        let ids: Vec<&str> = search.tracks.items.iter().map(|t| t.id.as_str()).collect();
+        let ids = ids.join(",");
+
+        let tracks = match self.clients.spotify.batch_track(&ids, token).await {
+            Ok(value) => value,
+            Err(e) => {
+                warn!(error = ?e, "spotify batch request failed");
+                sleep(Duration::from_millis(self.limits.queue_poll_ms))
+                    .await;
+                return false;
+            }
+        };
 
+        let count = self.ingest_tracks(tracks.tracks.into_iter().flatten().collect()).await;
         if count > 0 {
             info!("feed.added {} new tracks from Spotify", count);
         }
-        true 
+        true
+    }
+
+    /// Resolves a batch of Spotify track IDs (as from `seed_page_ids`) and
+    /// ingests them in chunks of 50 (Spotify's `tracks?ids=` batch cap).
+    /// Returns how many tracks were added.
+    async fn seed_from_ids(&self, ids: Vec<String>, token: &str) -> Result<usize, CrawlerError> {
+        let mut total = 0;
+        for chunk in ids.chunks(50) {
+            let csv = chunk.join(",");
+            let tracks = self.clients.spotify.batch_track(&csv, token).await?;
+            total += self.ingest_tracks(tracks.tracks.into_iter().flatten().collect()).await;
+        }
+        Ok(total)
+    }
+
+    /// Fetches one page of a playlist/album seed walk, returning the track
+    /// IDs it held (empty once the walk is exhausted).
+    async fn seed_page_ids(
+        &self, kind: SeedKind, seed_id: &str, offset: u32, token: &str
+    ) -> Result<Vec<String>, CrawlerError> {
+        match kind {
+            SeedKind::Playlist => {
+                let page = self.clients.spotify
+                    .playlist_tracks_page(seed_id, self.limits.seed_page_size, offset, token)
+                    .await?;
+                Ok(page.items.into_iter()
+                    .filter_map(|item| item.track.and_then(|t| t.id))
+                    .collect())
+            }
+            SeedKind::Album => {
+                let page = self.clients.spotify
+                    .album_tracks_page(seed_id, self.limits.seed_page_size, offset, token)
+                    .await?;
+                Ok(page.items.into_iter().filter_map(|item| item.id).collect())
+            }
+            SeedKind::Feed => Err(CrawlerError::Config(
+                "seed_page_ids does not handle SeedKind::Feed - see feed_exhaustive_step".into()
+            )),
+        }
+    }
+
+    /// Walks a playlist/album page by page, ingesting each page as it's
+    /// fetched and (when `--resume` is on) persisting the offset after every
+    /// page so a killed process restarts mid-walk instead of from the top.
+    async fn seed_paged(&self, kind: SeedKind, seed_id: &str, token: &str) ->
+        Result<usize, CrawlerError> {
+        let mut offset = if self.resume {
+            self.db.get_seed_cursor(kind, seed_id).await?
+        } else {
+            0
+        };
+
+        let mut total = 0;
+        loop {
+            let ids = self.seed_page_ids(kind, seed_id, offset, token).await?;
+            if ids.is_empty() {
+                break;
+            }
+
+            total += self.seed_from_ids(ids, token).await?;
+            offset += self.limits.seed_page_size;
+
+            if self.resume {
+                self.db.set_seed_cursor(kind, seed_id, offset).await?;
+            }
+            if total >= self.limits.seed_track_cap {
+                break;
+            }
+        }
+
+        if self.resume {
+            self.db.clear_seed_cursor(kind, seed_id).await?;
+        }
+        Ok(total)
+    }
+
+    /// Seeds the crawl queue from every track in a Spotify playlist.
+    pub async fn seed_playlist(&self, playlist_id: &str) -> Result<usize, CrawlerError> {
+        let (token, _) = Self::refresh_token(&self.clients.spotify).await?;
+        let total = self.seed_paged(SeedKind::Playlist, playlist_id, &token).await?;
+        info!(playlist_id, tracks = total, "seed.playlist.paged");
+        Ok(total)
+    }
+
+    /// Seeds the crawl queue from every track on a Spotify album.
+    pub async fn seed_album(&self, album_id: &str) -> Result<usize, CrawlerError> {
+        let (token, _) = Self::refresh_token(&self.clients.spotify).await?;
+        let total = self.seed_paged(SeedKind::Album, album_id, &token).await?;
+        info!(album_id, tracks = total, "seed.album.paged");
+        Ok(total)
+    }
+
+    /// The original feed strategy: sample a random year bucket and a random
+    /// offset within it, search, and ingest whatever comes back. Probabilistic
+    /// coverage - the same window can be resampled and others never touched.
+    async fn feed_random_step(&self, token: &str) -> bool {
+        let year: u32 = SmallRng::from_entropy().gen_range(1950..=2025);
+        let offset: u32 = SmallRng::from_entropy().gen_range(0..1000);
+        let query = format!("year:{year}");
+        debug!(%query, %offset, "spotify search");
+
+        let search = match self.clients.spotify.search(&query, 50_u32, offset, token).await {
+            Ok(value) => value,
+            Err(e) => {
+                warn!(error = ?e, "spotify search failed");
+                return false;
+            }
+        };
+
+        if !self.insert_tracks(search, token).await {
+            warn!("insert_tracks failed");
+            return false;
+        }
+        true
+    }
+
+    /// Deterministic alternative to `feed_random_step`: pages through each
+    /// `year:{year}` bucket in fixed 50-item chunks, oldest year first, until
+    /// a page comes back empty or Spotify's 1000-result search ceiling is
+    /// hit, then rolls over to the next year (wrapping back to 1950 after
+    /// 2025). The (year, offset) cursor is persisted in `seed_cursors` under
+    /// `SeedKind::Feed` after every page, so a restart resumes the sweep
+    /// instead of starting over - this guarantees full coverage of each year
+    /// bucket instead of the probabilistic holes/duplicates `feed_random_step`
+    /// can leave.
+    async fn feed_exhaustive_step(&self, token: &str) -> Result<(), CrawlerError> {
+        const FIRST_YEAR: u32 = 1950;
+        const LAST_YEAR: u32 = 2025;
+        const RESULT_CEILING: u32 = 1000;
+        const YEAR_CURSOR_ID: &str = "__year__";
+
+        let year = self.db.get_seed_cursor(SeedKind::Feed, YEAR_CURSOR_ID).await?;
+        let year = if year == 0 { FIRST_YEAR } else { year };
+        let query = format!("year:{year}");
+        let offset = self.db.get_seed_cursor(SeedKind::Feed, &query).await?;
+        debug!(%query, %offset, "feed.exhaustive.search");
+
+        let search = self.clients.spotify.search(&query, self.limits.seed_page_size, offset, token).await?;
+        let exhausted = search.tracks.items.is_empty()
+            || offset + self.limits.seed_page_size >= RESULT_CEILING;
+
+        if !search.tracks.items.is_empty() {
+            self.insert_tracks(search, token).await;
+        }
+
+        if exhausted {
+            self.db.clear_seed_cursor(SeedKind::Feed, &query).await?;
+            let next_year = if year >= LAST_YEAR { FIRST_YEAR } else { year + 1 };
+            self.db.set_seed_cursor(SeedKind::Feed, YEAR_CURSOR_ID, next_year).await?;
+            info!(year, "feed.exhaustive.year_done");
+        } else {
+            self.db.set_seed_cursor(SeedKind::Feed, &query, offset + self.limits.seed_page_size).await?;
+        }
+
+        Ok(())
     }
 
     async fn feed_loop(&self) {
@@ -639,12 +1101,7 @@ impl Crawler {
             }
 
             if bearer_token.is_none() || tokio::time::Instant::now() >= token_expiry {
-                match Self::refresh_token(
-                    &self.clients.spotify, 
-                    &self.clients.spotify.cfg,
-                    self.limits.http_max_retry,
-                    self.limits.http_backoff_ms
-                ).await {
+                match Self::refresh_token(&self.clients.spotify).await {
                     Ok((token, exp)) => {
                         bearer_token = Some(token);
                         token_expiry = exp; 
@@ -657,36 +1114,22 @@ impl Crawler {
                     }
                 }
             }
-            if bearer_token.is_some() {
-                let year: u32 = SmallRng::from_entropy().gen_range(1950..=2025);
-                let offset: u32 = SmallRng::from_entropy().gen_range(0..1000);
-                let query = format!("year:{year}");
-                debug!(%query, %offset, "spotify search");
-
-                let search = http_with_retry(
-                    self.clients.spotify.search(
-                        &query, 
-                        50_u32, 
-                        offset, 
-                        bearer_token.as_ref().unwrap()
-                    ), 
-                    self.limits.http_max_retry,
-                    self.limits.http_backoff_ms
-                ).await;
-                
-                let search = match search {
-                    Ok(value) => value, 
-                    Err(e) => {
-                        warn!(error = ?e, "spotify search failed");
-                        sleep(Duration::from_millis(self.limits.queue_poll_ms)).await;
-                        continue; 
+            if let Some(token) = bearer_token.as_deref() {
+                let ok = if self.feed.exhaustive {
+                    match self.feed_exhaustive_step(token).await {
+                        Ok(()) => true,
+                        Err(e) => {
+                            warn!(error = ?e, "feed.exhaustive_step failed");
+                            false
+                        }
                     }
+                } else {
+                    self.feed_random_step(token).await
                 };
-                let token = bearer_token.as_deref().unwrap(); 
-                if !self.insert_tracks(search, token).await {
-                    warn!("insert_tracks failed");
-                    sleep(Duration::from_millis(self.limits.queue_poll_ms)).await; 
-                    continue; 
+
+                if !ok {
+                    sleep(Duration::from_millis(self.limits.queue_poll_ms)).await;
+                    continue;
                 }
             }
             sleep(Duration::from_millis(self.limits.queue_poll_ms)).await; 
@@ -0,0 +1,69 @@
+//!
+//! src/errors.rs  Andrew Belles  Sept 13th, 2025
+//!
+//! Defines enums and methods of error conversion
+//! for errors the crawler uses
+//!
+//!
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CrawlerError {
+    #[error("config error: {0}")]
+    Config(String),
+    #[error("http error: {0}")]
+    Http(String),
+    /// A response with a numeric HTTP status `fetch::send_with_retry` judged
+    /// non-retryable (not in `RetryConfig::retryable_statuses`) - a permanent
+    /// 4xx like 400/404/410, as opposed to a transport error or a 429/5xx
+    /// that's still worth another attempt. Keeps the status code around so
+    /// `severity()` can tell the two apart.
+    #[error("http status {status}: {message}")]
+    HttpStatus { status: u16, message: String },
+    #[error("parse error: {0}")]
+    Parse(String),
+    #[error("not found: {0}")]
+    NotFound(String),
+    #[error("db error: {0}")]
+    Db(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error)
+}
+
+/// Whether a worker should retry the job that produced this error or give up
+/// on it immediately. See `crawler::Crawler::handle_job_failure`: a
+/// `Transient` error gets requeued with backoff up to `job_max_attempts`,
+/// a `Fatal` one is dead-lettered on the spot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSeverity {
+    Transient,
+    Fatal
+}
+
+impl CrawlerError {
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            // Network hiccups, 429s, 5xx, lock contention: worth another try.
+            CrawlerError::Http(_) | CrawlerError::Db(_) | CrawlerError::Io(_) =>
+                ErrorSeverity::Transient,
+            // Bad config, a 404/no-recording result, malformed JSON, or a
+            // permanent 4xx status won't fix itself on retry.
+            CrawlerError::Config(_) | CrawlerError::Parse(_) | CrawlerError::NotFound(_)
+            | CrawlerError::HttpStatus { .. } =>
+                ErrorSeverity::Fatal,
+        }
+    }
+}
+
+impl From<reqwest::Error> for CrawlerError {
+    fn from(e: reqwest::Error) -> Self { CrawlerError::Http(e.to_string()) }
+}
+
+impl From<serde_json::Error> for CrawlerError {
+    fn from(e: serde_json::Error) -> Self { CrawlerError::Parse(e.to_string()) }
+}
+
+impl From<sqlx::Error> for CrawlerError {
+    fn from(e: sqlx::Error) -> Self { CrawlerError::Db(e.to_string()) }
+}
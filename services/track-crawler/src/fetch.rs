@@ -0,0 +1,670 @@
+//!
+//! src/fetch.rs  Andrew Belles  Sept 10th, 2025
+//!
+//! Defines methods for hitting specified endpoints and returning unparsed
+//! data. `send_with_retry` is the one place every `*Client` routes a
+//! `RequestBuilder` through before executing it: a 429, or a 5xx that sends
+//! one, sleeps for at least the `Retry-After` the server sent (seconds or an
+//! HTTP-date), taking whichever is longer against the usual
+//! exponential-with-jitter backoff; a transport error, or a 5xx without
+//! `Retry-After`, backs off purely on the latter, up to
+//! `RetryConfig::max_attempts`. Every client holds its own per-host
+//! `RateLimiter`, which widens its spacing (AIMD-style) whenever that host
+//! answers 429 and relaxes it back toward the configured baseline after a
+//! run of clean responses, so a long crawl survives instead of aborting or
+//! hammering a service that's asked us to slow down.
+//!
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+use reqwest::{header, redirect, Client, RequestBuilder, Response};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+use tracing::warn;
+
+use crate::config::{
+    AcoustIdConfig, AcousticBrainzConfig, HttpConfig, IdentityConfig, LastFmConfig,
+    MusicBrainzConfig, RetryConfig, SpotifyConfig,
+};
+use crate::errors::CrawlerError;
+use crate::ids::{Isrc, Mbid, SpotifyId};
+use crate::models;
+use crate::status::ClientStats;
+
+/// Successive successes required before `on_success` relaxes the rate back
+/// up one notch.
+const AIMD_RELAX_STREAK: u32 = 20;
+/// Multiplicative cut applied to the current rate the moment a 429 is seen.
+const AIMD_BACKOFF_FACTOR: f64 = 0.5;
+/// How low the rate is allowed to sink relative to `baseline_rate`.
+const AIMD_FLOOR_FRACTION: f64 = 0.05;
+/// Additive step (as a fraction of the baseline rate) restored per relax.
+const AIMD_RELAX_STEP: f64 = 0.1;
+
+/// Token-bucket limiter: refills at the current rate (tokens a second), up
+/// to `burst` tokens, and `acquire()`s until a token is available. One of these
+/// is held per client/host so MusicBrainz's 1 req/s cap doesn't steal budget
+/// from Spotify's much higher one.
+///
+/// The rate itself is AIMD-adaptive: `on_rate_limited` halves it the moment
+/// a service answers 429 (so a crawl backs off from a service that just
+/// told us to slow down), and `on_success` creeps it back toward the
+/// configured baseline after a run of clean responses. It never exceeds that
+/// baseline, since that's the operator's stated policy for the service.
+pub struct RateLimiter {
+    baseline_rate: f64,
+    burst: f64,
+    rate: Mutex<f64>,
+    tokens: Mutex<f64>,
+    last_refill: Mutex<Instant>,
+    success_streak: Mutex<u32>,
+}
+
+impl RateLimiter {
+    pub fn new(rate_per_sec: f32, burst: u32) -> Self {
+        let burst = burst.max(1) as f64;
+        let baseline_rate = rate_per_sec.max(0.01) as f64;
+        Self {
+            baseline_rate,
+            burst,
+            rate: Mutex::new(baseline_rate),
+            tokens: Mutex::new(burst),
+            last_refill: Mutex::new(Instant::now()),
+            success_streak: Mutex::new(0),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        loop {
+            let rate = *self.rate.lock().await;
+            {
+                let mut tokens = self.tokens.lock().await;
+                let mut last = self.last_refill.lock().await;
+                let elapsed = last.elapsed().as_secs_f64();
+                *tokens = (*tokens + elapsed * rate).min(self.burst);
+                *last = Instant::now();
+
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+            }
+            let wait_secs = (1.0 / rate).max(0.001);
+            sleep(Duration::from_secs_f64(wait_secs)).await;
+        }
+    }
+
+    /// AIMD multiplicative decrease: called when the service answers 429.
+    pub async fn on_rate_limited(&self) {
+        let mut rate = self.rate.lock().await;
+        *rate = (*rate * AIMD_BACKOFF_FACTOR).max(self.baseline_rate * AIMD_FLOOR_FRACTION);
+        *self.success_streak.lock().await = 0;
+    }
+
+    /// AIMD additive increase: called on every successful response. Nudges
+    /// the rate back toward (never past) `baseline_rate` once
+    /// `AIMD_RELAX_STREAK` clean responses have landed in a row.
+    pub async fn on_success(&self) {
+        let mut streak = self.success_streak.lock().await;
+        *streak += 1;
+        if *streak >= AIMD_RELAX_STREAK {
+            *streak = 0;
+            let mut rate = self.rate.lock().await;
+            *rate = (*rate + self.baseline_rate * AIMD_RELAX_STEP).min(self.baseline_rate);
+        }
+    }
+}
+
+pub(crate) fn generate_backoff(base: Duration, attempt: u32, jitter: bool) -> Duration {
+    let exp = base.as_millis() as u64 * (1_u64 << attempt.min(6));
+    let with_jitter = if jitter {
+        let extra = rand::thread_rng().gen_range(0..=base.as_millis() as u64 / 2 + 1);
+        exp + extra
+    } else {
+        exp
+    };
+    Duration::from_millis(with_jitter)
+}
+
+/// Parses a `Retry-After` header value, which per RFC 9110 is either a
+/// delta-seconds integer or an IMF-fixdate HTTP-date
+/// (`Sun, 06 Nov 1994 08:49:37 GMT`).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let target = parse_imf_fixdate(value)?;
+    Some(Duration::from_secs(target.saturating_sub(unix_now())))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Days since the Unix epoch for a given civil (proleptic Gregorian) date,
+/// per Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn parse_imf_fixdate(value: &str) -> Option<u64> {
+    let parts: Vec<&str> = value.split_whitespace().collect();
+    if parts.len() != 6 || parts[5] != "GMT" {
+        return None;
+    }
+
+    let day: i64 = parts[1].parse().ok()?;
+    let month = match parts[2] {
+        "Jan" => 1, "Feb" => 2, "Mar" => 3, "Apr" => 4,
+        "May" => 5, "Jun" => 6, "Jul" => 7, "Aug" => 8,
+        "Sep" => 9, "Oct" => 10, "Nov" => 11, "Dec" => 12,
+        _ => return None,
+    };
+    let year: i64 = parts[3].parse().ok()?;
+
+    let mut clock = parts[4].split(':');
+    let hour: i64 = clock.next()?.parse().ok()?;
+    let min: i64 = clock.next()?.parse().ok()?;
+    let sec: i64 = clock.next()?.parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let secs = days * 86_400 + hour * 3_600 + min * 60 + sec;
+    u64::try_from(secs).ok()
+}
+
+fn retry_after_from(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+/// Sends `request` through `limiter` (if any), retrying on 429/5xx/transport
+/// errors according to `retry`. Only call this for idempotent requests
+/// (GETs, or the Spotify client-credentials token POST) — set
+/// `idempotent = false` to disable retries on anything with side effects.
+pub async fn send_with_retry(
+    request: RequestBuilder,
+    retry: &RetryConfig,
+    limiter: Option<&RateLimiter>,
+    stats: Option<&ClientStats>,
+    idempotent: bool,
+) -> Result<Response, CrawlerError> {
+    let mut attempt: u32 = 0;
+
+    loop {
+        if let Some(limiter) = limiter {
+            limiter.acquire().await;
+        }
+
+        let cloned = request
+            .try_clone()
+            .ok_or_else(|| CrawlerError::Http("non-cloneable request".to_string()))?;
+
+        if let Some(stats) = stats {
+            stats.requests.fetch_add(1, Ordering::Relaxed);
+        }
+        let response = cloned.send().await;
+
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                if let Some(limiter) = limiter {
+                    limiter.on_success().await;
+                }
+                return Ok(resp);
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                if status.as_u16() == 429 {
+                    if let Some(stats) = stats {
+                        stats.rate_limited.fetch_add(1, Ordering::Relaxed);
+                    }
+                    if let Some(limiter) = limiter {
+                        limiter.on_rate_limited().await;
+                    }
+                }
+                // Whether this status is worth retrying at all (a permanent
+                // 4xx like 400/404/410 never is, regardless of idempotency)
+                // versus whether we retry it *here* (only for idempotent
+                // requests - a non-idempotent call with a retryable status
+                // still isn't classified as permanently fatal, since a later
+                // job-level attempt of the whole operation could succeed).
+                let status_retryable = retry.retryable_statuses.contains(&status.as_u16());
+                if !status_retryable {
+                    return Err(CrawlerError::HttpStatus {
+                        status: status.as_u16(),
+                        message: format!("non-retryable status {status}"),
+                    });
+                }
+
+                let retryable = idempotent && status_retryable;
+                if !retryable || attempt >= retry.max_attempts as u32 {
+                    return Err(CrawlerError::Http(format!(
+                        "status {status} after {attempt} retries"
+                    )));
+                }
+
+                let server_hint = retry.respect_retry_after
+                    .then(|| retry_after_from(resp.headers()))
+                    .flatten()
+                    .map(|d| d.min(retry.retry_after_cap));
+                let computed = generate_backoff(retry.base_backoff, attempt, retry.jitter);
+                let backoff = server_hint.map_or(computed, |hint| hint.max(computed));
+                warn!(
+                    status = %status, backoff_ms = backoff.as_millis(),
+                    server_hint = server_hint.is_some(), "fetch.retry"
+                );
+                if let Some(stats) = stats {
+                    stats.retries.fetch_add(1, Ordering::Relaxed);
+                }
+                sleep(backoff).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                if !idempotent || attempt >= retry.max_attempts as u32 {
+                    return Err(e.into());
+                }
+                let backoff = generate_backoff(retry.base_backoff, attempt, retry.jitter);
+                warn!(backoff_ms = backoff.as_millis(), "fetch.retry.transport");
+                if let Some(stats) = stats {
+                    stats.retries.fetch_add(1, Ordering::Relaxed);
+                }
+                sleep(backoff).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+fn client_helper(http: &HttpConfig) -> reqwest::ClientBuilder {
+    Client::builder()
+        .timeout(http.timeout)
+        .connect_timeout(http.connect_timeout)
+        .pool_max_idle_per_host(http.pool_max_idle_per_host)
+        .pool_idle_timeout(Some(http.pool_idle_timeout))
+        .redirect(redirect::Policy::limited(http.max_redirects as usize))
+}
+
+fn client_with_headers(http: &HttpConfig, headers: header::HeaderMap) ->
+    Result<Client, CrawlerError> {
+    client_helper(http)
+        .default_headers(headers)
+        .build()
+        .map_err(|e| CrawlerError::Http(format!("build client: {e}")))
+}
+
+pub fn base_client(http: &HttpConfig) -> Result<Client, CrawlerError> {
+    let mut h = header::HeaderMap::new();
+    h.insert(header::ACCEPT, header::HeaderValue::from_static("application/json"));
+    client_with_headers(http, h)
+}
+
+fn mb_family_client(http: &HttpConfig, id: &IdentityConfig) -> Result<Client, CrawlerError> {
+    let mut h = header::HeaderMap::new();
+    h.insert(header::ACCEPT, header::HeaderValue::from_static("application/json"));
+    h.insert(
+        header::USER_AGENT,
+        header::HeaderValue::from_str(&id.mb_user_agent)
+            .map_err(|e| CrawlerError::Config(format!("invalid mb user-agent {e}")))?
+    );
+    client_with_headers(http, h)
+}
+
+#[derive(Clone, Debug)]
+pub struct SpotifyClient {
+    pub http: Client,
+    pub cfg: SpotifyConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
+    pub stats: Arc<ClientStats>,
+}
+
+impl SpotifyClient {
+    pub fn new(http_config: &HttpConfig, cfg: &SpotifyConfig) -> Result<Self, CrawlerError> {
+        let http = base_client(http_config)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_config.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(cfg.rate_limit.max_rps, cfg.rate_limit.burst)),
+            stats: Arc::new(ClientStats::new()),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), Some(&self.stats), idempotent).await
+    }
+
+    fn token_request(&self) -> RequestBuilder {
+        self.http
+            .post(self.cfg.token_url.clone())
+            .basic_auth(&self.cfg.client_id, Some(&self.cfg.client_secret))
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body("grant_type=client_credentials")
+    }
+
+    /// POST {token_url} (client-credentials grant)
+    pub async fn request_token(&self) -> Result<models::SpotifyTokenResponse, CrawlerError> {
+        let request = self.token_request();
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::SpotifyTokenResponse>().await?)
+    }
+
+    fn track_request(&self, track_id: &SpotifyId, bearer: &str) -> RequestBuilder {
+        let url = self.cfg.api_base.join(&format!("tracks/{track_id}")).unwrap();
+        self.http.get(url).bearer_auth(bearer)
+    }
+
+    /// GET /v1/tracks/{id}
+    pub async fn track(&self, track_id: &SpotifyId<'_>, bearer: &str) -> Result<models::SpotifyTrack, CrawlerError> {
+        let request = self.track_request(track_id, bearer);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::SpotifyTrack>().await?)
+    }
+
+    fn batch_track_request(&self, ids_csv: &str, bearer: &str) -> RequestBuilder {
+        let url = self.cfg.api_base.join("tracks").unwrap();
+        self.http.get(url).bearer_auth(bearer).query(&[("ids", ids_csv)])
+    }
+
+    /// GET /v1/tracks?ids=...
+    pub async fn batch_track(&self, ids_csv: &str, bearer: &str) ->
+        Result<models::SpotifyTracksResponse, CrawlerError> {
+        let request = self.batch_track_request(ids_csv, bearer);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::SpotifyTracksResponse>().await?)
+    }
+
+    fn search_request(&self, query: &str, limit: u32, offset: u32, bearer: &str) -> RequestBuilder {
+        let url = self.cfg.api_base.join("search").unwrap();
+        self.http.get(url).bearer_auth(bearer).query(&[
+            ("type", "track"),
+            ("q", query),
+            ("limit", &limit.to_string()),
+            ("offset", &offset.to_string())
+        ])
+    }
+
+    /// GET /v1/search?type=track&q=...&limit=&offset=
+    pub async fn search(&self, query: &str, limit: u32, offset: u32, bearer: &str) ->
+        Result<models::SpotifySearchResponse, CrawlerError> {
+        let request = self.search_request(query, limit, offset, bearer);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::SpotifySearchResponse>().await?)
+    }
+
+    fn playlist_tracks_request(&self, playlist_id: &str, limit: u32, offset: u32, bearer: &str) ->
+        RequestBuilder {
+        let url = self.cfg.api_base.join(&format!("playlists/{playlist_id}/tracks")).unwrap();
+        self.http.get(url).bearer_auth(bearer).query(&[
+            ("limit", &limit.to_string()),
+            ("offset", &offset.to_string()),
+        ])
+    }
+
+    /// One page of GET /v1/playlists/{id}/tracks?limit=&offset=. Exposed as
+    /// `pub` (rather than wrapped in a single all-pages call) so the caller
+    /// can persist `offset` between pages and resume a killed seed walk.
+    pub async fn playlist_tracks_page(&self, playlist_id: &str, limit: u32, offset: u32, bearer: &str) ->
+        Result<models::SpotifyPlaylistTracksPage, CrawlerError> {
+        let request = self.playlist_tracks_request(playlist_id, limit, offset, bearer);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::SpotifyPlaylistTracksPage>().await?)
+    }
+
+    fn album_tracks_request(&self, album_id: &str, limit: u32, offset: u32, bearer: &str) ->
+        RequestBuilder {
+        let url = self.cfg.api_base.join(&format!("albums/{album_id}/tracks")).unwrap();
+        self.http.get(url).bearer_auth(bearer).query(&[
+            ("limit", &limit.to_string()),
+            ("offset", &offset.to_string()),
+        ])
+    }
+
+    /// One page of GET /v1/albums/{id}/tracks?limit=&offset=. `pub` for the
+    /// same reason as `playlist_tracks_page` — resumable seed walks need the
+    /// caller to own the offset between pages.
+    pub async fn album_tracks_page(&self, album_id: &str, limit: u32, offset: u32, bearer: &str) ->
+        Result<models::SpotifyAlbumTracksPage, CrawlerError> {
+        let request = self.album_tracks_request(album_id, limit, offset, bearer);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::SpotifyAlbumTracksPage>().await?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MusicBrainzClient {
+    pub http: Client,
+    pub cfg: MusicBrainzConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
+    pub stats: Arc<ClientStats>,
+}
+
+impl MusicBrainzClient {
+    pub fn new(http_config: &HttpConfig, id: &IdentityConfig, cfg: &MusicBrainzConfig) ->
+        Result<Self, CrawlerError> {
+        let http = mb_family_client(http_config, id)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_config.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(cfg.rate_limit.max_rps, cfg.rate_limit.burst)),
+            stats: Arc::new(ClientStats::new()),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), Some(&self.stats), idempotent).await
+    }
+
+    fn lookup_isrc_request(&self, isrc: &Isrc) -> RequestBuilder {
+        let url = self.cfg.base_url.join(&format!("isrc/{isrc}?fmt=json")).unwrap();
+        self.http.get(url)
+    }
+
+    /// GET /ws/2/isrc/{ISRC}?fmt=json
+    pub async fn lookup_isrc(&self, isrc: &Isrc<'_>) -> Result<models::MbIsrcLookup, CrawlerError> {
+        let request = self.lookup_isrc_request(isrc);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::MbIsrcLookup>().await?)
+    }
+
+    fn search_recording_request(&self, lucene: &str, limit: u32, offset: u32) -> RequestBuilder {
+        let url = self.cfg.base_url.join("recording").unwrap();
+        self.http.get(url).query(&[
+            ("query", lucene),
+            ("fmt", "json"),
+            ("limit", &limit.to_string()),
+            ("offset", &offset.to_string())
+        ])
+    }
+
+    /// GET /ws/2/recording?query=...&fmt=json&limit=&offset=
+    pub async fn search_recording(&self, lucene: &str, limit: u32, offset: u32) ->
+        Result<models::MbIsrcLookup, CrawlerError> {
+        let request = self.search_recording_request(lucene, limit, offset);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::MbIsrcLookup>().await?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AcousticBrainzClient {
+    pub http: Client,
+    pub cfg: AcousticBrainzConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
+    pub stats: Arc<ClientStats>,
+}
+
+impl AcousticBrainzClient {
+    pub fn new(
+        http_config: &HttpConfig,
+        identity: &IdentityConfig,
+        cfg: &AcousticBrainzConfig
+    ) -> Result<Self, CrawlerError> {
+        let http = mb_family_client(http_config, identity)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_config.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(cfg.rate_limit.max_rps, cfg.rate_limit.burst)),
+            stats: Arc::new(ClientStats::new()),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), Some(&self.stats), idempotent).await
+    }
+
+    fn features_request(&self, mb_recording_id: &Mbid, level: &str) -> RequestBuilder {
+        let url = self.cfg.base_url.join(&format!("api/v1/{mb_recording_id}/{level}")).unwrap();
+        self.http.get(url)
+    }
+
+    /// GET {base}/api/v1/{mbid}/high-level
+    pub async fn high_level(&self, mb_recording_id: &Mbid<'_>) ->
+        Result<models::AcousticHighLevel, CrawlerError> {
+        let request = self.features_request(mb_recording_id, "high-level");
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::AcousticHighLevel>().await?)
+    }
+
+    /// GET {base}/api/v1/{mbid}/low-level
+    pub async fn low_level(&self, mb_recording_id: &Mbid<'_>) ->
+        Result<models::AcousticLowLevel, CrawlerError> {
+        let request = self.features_request(mb_recording_id, "low-level");
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::AcousticLowLevel>().await?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LastFmClient {
+    pub http: Client,
+    pub cfg: LastFmConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
+    pub stats: Arc<ClientStats>,
+}
+
+impl LastFmClient {
+    pub fn new(http_cfg: &HttpConfig, cfg: &LastFmConfig) -> Result<Self, CrawlerError> {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::ACCEPT, header::HeaderValue::from_static("application/json"));
+        let http = client_with_headers(http_cfg, headers)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_cfg.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(cfg.rate_limit.max_rps, cfg.rate_limit.burst)),
+            stats: Arc::new(ClientStats::new()),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), Some(&self.stats), idempotent).await
+    }
+
+    fn track_top_tags_request(&self, artist: &str, track: &str) -> RequestBuilder {
+        self.http.get(self.cfg.base_url.clone()).query(&[
+            ("method", "track.getTopTags"),
+            ("artist", artist),
+            ("track", track),
+            ("api_key", self.cfg.api_key.as_str()),
+            ("format", "json"),
+        ])
+    }
+
+    /// GET /?method=track.getTopTags&artist=...&track=...&api_key=...&format=json
+    pub async fn track_top_tags(&self, artist: &str, track: &str) ->
+        Result<models::LastFmTopTags, CrawlerError> {
+        let request = self.track_top_tags_request(artist, track);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::LastFmTopTags>().await?)
+    }
+
+    fn track_top_tags_by_mbid_request(&self, mbid: &str) -> RequestBuilder {
+        self.http.get(self.cfg.base_url.clone()).query(&[
+            ("method", "track.getTopTags"),
+            ("mbid", mbid),
+            ("api_key", self.cfg.api_key.as_str()),
+            ("format", "json"),
+        ])
+    }
+
+    /// GET /?method=track.getTopTags&mbid=...&api_key=...&format=json
+    pub async fn track_top_tags_by_mbid(&self, mbid: &str) ->
+        Result<models::LastFmTopTags, CrawlerError> {
+        let request = self.track_top_tags_by_mbid_request(mbid);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::LastFmTopTags>().await?)
+    }
+}
+
+pub struct AcoustIdClient {
+    pub http: Client,
+    pub cfg: AcoustIdConfig,
+    pub retry: RetryConfig,
+    pub limiter: Arc<RateLimiter>,
+    pub stats: Arc<ClientStats>,
+}
+
+impl AcoustIdClient {
+    pub fn new(http_cfg: &HttpConfig, cfg: &AcoustIdConfig) -> Result<Self, CrawlerError> {
+        let http = base_client(http_cfg)?;
+        Ok( Self {
+            http,
+            cfg: cfg.clone(),
+            retry: http_cfg.retry.clone(),
+            limiter: Arc::new(RateLimiter::new(cfg.rate_limit.max_rps, cfg.rate_limit.burst)),
+            stats: Arc::new(ClientStats::new()),
+        })
+    }
+
+    /// Runs `request` through the shared rate-limiter/retry wrapper.
+    pub async fn send(&self, request: RequestBuilder, idempotent: bool) ->
+        Result<Response, CrawlerError> {
+        send_with_retry(request, &self.retry, Some(&self.limiter), Some(&self.stats), idempotent).await
+    }
+
+    fn lookup_fingerprint_request(&self, fingerprint: &str, duration_secs: u32) -> RequestBuilder {
+        let url = self.cfg.base_url.join("lookup").unwrap();
+        self.http.get(url).query(&[
+            ("client", self.cfg.api_key.as_str()),
+            ("meta", "recordingids"),
+            ("duration", &duration_secs.to_string()),
+            ("fingerprint", fingerprint),
+        ])
+    }
+
+    /// GET {base}/lookup?client=...&meta=recordingids&duration=...&fingerprint=...
+    pub async fn lookup_fingerprint(&self, fingerprint: &str, duration_secs: u32) ->
+        Result<models::AcoustIdLookup, CrawlerError> {
+        let request = self.lookup_fingerprint_request(fingerprint, duration_secs);
+        let response = self.send(request, true).await?;
+        Ok(response.json::<models::AcoustIdLookup>().await?)
+    }
+}
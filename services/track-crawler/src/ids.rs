@@ -0,0 +1,91 @@
+//!
+//! src/ids.rs  Andrew Belles  Sept 15th, 2025
+//!
+//! Validated, `Cow`-backed newtypes for the three identifier formats this
+//! crate passes between services: Spotify's base62 track IDs, ISRCs, and
+//! MusicBrainz UUIDs. Wrapping each in its own type stops an ISRC from
+//! being handed to a method that expects an MBID, since the compiler
+//! catches the mismatch instead of a 404 at request time.
+//!
+//!
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer};
+
+use crate::errors::CrawlerError;
+
+macro_rules! validated_id {
+    ($name:ident, $desc:literal, $validate:path) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name<'a>(Cow<'a, str>);
+
+        impl<'a> $name<'a> {
+            pub fn from_str(s: &'a str) -> Result<Self, CrawlerError> {
+                if $validate(s) {
+                    Ok(Self(Cow::Borrowed(s)))
+                } else {
+                    Err(CrawlerError::Parse(format!("invalid {}: {s}", $desc)))
+                }
+            }
+
+            pub fn into_owned(self) -> $name<'static> {
+                $name(Cow::Owned(self.0.into_owned()))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name<'static> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let s = String::deserialize(deserializer)?;
+                if $validate(&s) {
+                    Ok(Self(Cow::Owned(s)))
+                } else {
+                    Err(de::Error::custom(format!("invalid {}: {s}", $desc)))
+                }
+            }
+        }
+    };
+}
+
+/// Spotify base62 IDs are always 22 alphanumeric characters.
+fn is_spotify_id(s: &str) -> bool {
+    s.len() == 22 && s.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// ISRC shape: CC-XXX-YY-NNNNN, written without hyphens as 12 characters —
+/// 2-letter country, 3 alphanumeric registrant, 2-digit year, 5-digit designation.
+fn is_isrc(s: &str) -> bool {
+    let b = s.as_bytes();
+    b.len() == 12
+        && b[0].is_ascii_alphabetic()
+        && b[1].is_ascii_alphabetic()
+        && b[2..5].iter().all(u8::is_ascii_alphanumeric)
+        && b[5..12].iter().all(u8::is_ascii_digit)
+}
+
+/// MusicBrainz IDs are UUIDs: 8-4-4-4-12 hex digits.
+fn is_mbid(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    parts.len() == 5
+        && [8, 4, 4, 4, 12].iter().zip(&parts).all(|(&len, p)| {
+            p.len() == len && p.chars().all(|c| c.is_ascii_hexdigit())
+        })
+}
+
+validated_id!(SpotifyId, "spotify id", is_spotify_id);
+validated_id!(Isrc, "isrc", is_isrc);
+validated_id!(Mbid, "mbid", is_mbid);
@@ -6,20 +6,30 @@
 //!
 //!
 
-mod crawler; 
-mod config; 
+mod cache;
+mod crawler;
+mod config;
 mod fetch;
-mod persistent; 
+mod ids;
+mod metrics;
+mod models;
+mod persistent;
 mod sink;
-mod logging; 
+mod status;
+mod logging;
 
-mod errors; 
+mod errors;
 use crate::errors::CrawlerError;
+use tracing::{error, info};
 
 #[tokio::main]
 async fn main() -> Result<(), CrawlerError> {
     let cfgs = config::load_config()?; 
-    let db   = persistent::Persistent::init("../data/raw.db").await?;
+    let db   = persistent::Persistent::init(persistent::ConnectionOptions::Fresh {
+        database_url: "../data/raw.db".to_string(),
+        disable_statement_logging: false,
+        max_connections: 8,
+    }).await?;
 
     let spotify = fetch::SpotifyClient::new(&cfgs.http, &cfgs.spotify)?;
     let musicbrainz = fetch::MusicBrainzClient::new(
@@ -33,7 +43,8 @@ async fn main() -> Result<(), CrawlerError> {
         &cfgs.acousticbrainz
     )?;
     let lastfm = fetch::LastFmClient::new(&cfgs.http, &cfgs.lastfm)?;
-    let clients = crawler::Clients::new(spotify, musicbrainz, acousticbrainz, lastfm);
+    let acoustid = fetch::AcoustIdClient::new(&cfgs.http, &cfgs.acoustid)?;
+    let clients = crawler::Clients::new(spotify, musicbrainz, acousticbrainz, lastfm, acoustid);
 
     let disk = sink::DiskZstdSink::new("../data/raw/", 3);
     let limits = crawler::CrawlerLimits::default();
@@ -41,6 +52,19 @@ async fn main() -> Result<(), CrawlerError> {
     let _logger = logging::init_logging(&cfgs.logging);
     let crawler = crawler::Crawler::new(&cfgs, db, clients, disk, limits);
 
+    if let Ok(playlist_id) = std::env::var("SEED_PLAYLIST_ID") {
+        match crawler.seed_playlist(&playlist_id).await {
+            Ok(n) => info!(playlist_id, added = n, "seed.playlist.done"),
+            Err(e) => error!(error = ?e, playlist_id, "seed.playlist.failed"),
+        }
+    }
+    if let Ok(album_id) = std::env::var("SEED_ALBUM_ID") {
+        match crawler.seed_album(&album_id).await {
+            Ok(n) => info!(album_id, added = n, "seed.album.done"),
+            Err(e) => error!(error = ?e, album_id, "seed.album.failed"),
+        }
+    }
+
     let () = crawler.run().await?;
 
     Ok(())
@@ -71,26 +95,13 @@ mod tests {
         let cfgs = config::load_config()?;
         let spotify = fetch::SpotifyClient::new(&cfgs.http, &cfgs.spotify)?;
 
-        let token_response = spotify.token_request()
-            .basic_auth(&cfgs.spotify.client_id, Some(&cfgs.spotify.client_secret))
-            .send()
-            .await?;
-        assert!(token_response.status().is_success());
-
-        let token: serde_json::Value = token_response.json().await?;
-        let bearer = token["access_token"].as_str().unwrap();
-
-        println!("token: {}",  serde_json::to_string_pretty(&token)?);
-        println!("bearer: {bearer}");
+        let token = spotify.request_token().await?;
+        println!("bearer: {}", token.access_token);
 
         // Breathe Deeper -  Tame Impala, Lil Yatchy
-        let track_response = spotify.track("6GtOsEzNUhJghrIf6UTbRV", bearer)
-            .send()
-            .await?;
-        assert!(track_response.status().is_success());
-
-        let track: serde_json::Value = track_response.json().await?;
-        println!("track: {}", serde_json::to_string_pretty(&track)?);
+        let track_id = ids::SpotifyId::from_str("6GtOsEzNUhJghrIf6UTbRV")?;
+        let track = spotify.track(&track_id, &token.access_token).await?;
+        println!("track: {track:#?}");
 
         Ok(())
     }
@@ -109,13 +120,9 @@ mod tests {
         let musicbrainz = fetch::MusicBrainzClient::new(
             &cfgs.http, &cfgs.identity, &cfgs.musicbrainz)?;
 
-        let response = musicbrainz.lookup_isrc("AUUM71900929")
-            .send()
-            .await?;
-        assert!(response.status().is_success());
-
-        let isrc: serde_json::Value = response.json().await?; 
-        println!("isrc: {}", serde_json::to_string_pretty(&isrc)?);
+        let isrc = ids::Isrc::from_str("AUUM71900929")?;
+        let lookup = musicbrainz.lookup_isrc(&isrc).await?;
+        println!("isrc: {lookup:#?}");
 
         Ok(())
     }
@@ -134,50 +141,23 @@ mod tests {
         eprintln!("cwd = {}", std::env::current_dir().unwrap().display());
         eprintln!("db  = {db_url}");
 
-        let persistent = crate::persistent::Persistent::init(db_url).await?;
+        let persistent = crate::persistent::Persistent::init(persistent::ConnectionOptions::Fresh {
+            database_url: db_url.to_string(),
+            disable_statement_logging: false,
+            max_connections: 8,
+        }).await?;
         let cfgs = config::load_config()?;
         let spotify = fetch::SpotifyClient::new(&cfgs.http, &cfgs.spotify)?;
 
-        let token_response = spotify.token_request()
-            .basic_auth(&cfgs.spotify.client_id, Some(&cfgs.spotify.client_secret))
-            .send()
-            .await?;
-        assert!(token_response.status().is_success());
-
-        let token: serde_json::Value = token_response.json().await?;
-        let bearer = token["access_token"].as_str().unwrap();
-
-        println!("token: {}",  serde_json::to_string_pretty(&token)?);
-        println!("bearer: {bearer}");
+        let token = spotify.request_token().await?;
 
         // Breathe Deeper -  Tame Impala, Lil Yatchy
-        let track_response = spotify.track("6GtOsEzNUhJghrIf6UTbRV", bearer)
-            .send()
-            .await?;
-        assert!(track_response.status().is_success());
-
-        let track_json: serde_json::Value = track_response.json().await?;
-        println!("track: {}", serde_json::to_string_pretty(&track_json)?);
-
-        let input = crate::persistent::SpotifyTrack {
-            spotify_id: Some(track_json["id"].as_str().unwrap().to_string()),
-            isrc: track_json["external_ids"]["isrc"].as_str().map(str::to_string),
-            title: track_json["name"].as_str().unwrap().to_string(),
-            artist_all: track_json["artists"].as_array()
-                .unwrap()
-                .iter()
-                .filter_map(|a| a["name"].as_str())
-                .map(str::to_string)
-                .collect(),
-            album: track_json["album"]["name"].as_str().map(str::to_string),
-            duration_ms: track_json["duration_ms"].as_i64(),
-            release_date: track_json["album"]["release_date"].as_str().map(
-                str::to_string),
-            explicit: track_json["explicit"].as_bool(),
-            popularity: track_json["popularity"].as_i64().map(|x| x as i32),
-        };
-
-        let (uuid, _) = persistent.upsert_track(&input).await?; 
+        let track_id = ids::SpotifyId::from_str("6GtOsEzNUhJghrIf6UTbRV")?;
+        let track = spotify.track(&track_id, &token.access_token).await?;
+        println!("track: {track:#?}");
+
+        let input = crate::persistent::SpotifyTrack::new(&track);
+        let (uuid, _) = persistent.upsert_track(&input).await?;
 
         let fetched = persistent.get_track_metadata(&uuid).await?
             .expect("track should exist");
@@ -194,9 +174,10 @@ mod tests {
         println!("row: \n{}", serde_json::to_string_pretty(&formatted)?);
 
         let sink = sink::DiskZstdSink::new("../data", 3);
-        let spotify_id = track_json["id"].as_str().unwrap(); 
+        let spotify_id = track.id.as_str();
+        let raw = serde_json::to_value(&track)?;
         let path = sink.write_json(
-            sink::RawType::SpotifyTrack, spotify_id, track_json.clone()
+            sink::RawType::SpotifyTrack, spotify_id, raw
         )?;
 
         println!("wrote raw data to {}", path.display());
@@ -223,107 +204,56 @@ mod tests {
         let lastfm  = fetch::LastFmClient::new(
             &cfgs.http, &cfgs.lastfm)?; 
 
-        let token_response = spotify.token_request()
-            .basic_auth(&cfgs.spotify.client_id, Some(&cfgs.spotify.client_secret))
-            .send()
-            .await?; 
+        let token = spotify.request_token().await?;
 
-        assert!(token_response.status().is_success(), 
-            "spotify token status: {}", token_response.status());
+        let track_id = ids::SpotifyId::from_str("6GtOsEzNUhJghrIf6UTbRV")?;
+        let track = spotify.track(&track_id, &token.access_token).await?;
+        println!("spotify.track:\n{track:#?}");
 
-        let token: serde_json::Value = token_response.json().await?; 
-        let bearer = token["access_token"].as_str().expect("spotify access_token missing");
+        let isrc = track.external_ids.isrc.clone()
+            .ok_or_else(|| CrawlerError::Parse(
+                    "spotify track missing external_ids".into())
+            )?;
+        let isrc = ids::Isrc::from_str(&isrc)?;
 
-        let track_id = "6GtOsEzNUhJghrIf6UTbRV";
-        let track_response = spotify.track(track_id, bearer).send().await?; 
+        let track_title = track.name.clone();
+        let first_artist = track.artists.first()
+            .map(|a| a.name.clone())
+            .unwrap_or_default();
 
-        assert!(track_response.status().is_success(), 
-            "spotify track status: {}", track_response.status());
+        // Hit MusicBrainz to get MBID from ISRC
 
-        let track: serde_json::Value = track_response.json().await?; 
-        println!("spotify.track:\n{}", serde_json::to_string_pretty(&track)?);
+        let mb = musicb.lookup_isrc(&isrc).await?;
+        println!("musicbrainz.isrc:\n{mb:#?}");
 
-        let isrc = track.pointer("/external_ids/isrc")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| CrawlerError::Parse(
-                    "spotify track missing external_ids".into())
-            )?.to_string(); 
-        
-        let track_title = track.get("name")
-            .and_then(|v| v.as_str())
-            .unwrap_or_default()
-            .to_string(); 
-
-        let artists_array = track.get("artists")
-            .and_then(|v| v.as_array())
-            .unwrap();
-
-        let first_artist = artists_array.first() 
-            .and_then(|a| a.get("name"))
-            .and_then(|v| v.as_str())
-            .unwrap_or_default()
-            .to_string();
-
-        // Hit MusicBrainz to get MBID from ISRC 
-
-        let mb_response = musicb.lookup_isrc(&isrc).send().await?; 
-        assert!(mb_response.status().is_success(), 
-            "musicbrainz isrc status: {}", mb_response.status()); 
-
-        let mb: serde_json::Value = mb_response.json().await?; 
-        println!("musicbrainz.isrc:\n{}", serde_json::to_string_pretty(&mb)?);
-
-        let mbid = mb.get("recordings")
-            .and_then(|v| v.as_array())
-            .and_then(|arr| arr.iter()
-                .find_map(|r| r.get("id").and_then(|v| v.as_str())))
+        let mbid = mb.recordings.first()
+            .map(|r| r.id.clone())
             .ok_or_else(|| CrawlerError::Parse(
                 "no recordings found for ISRC".into()
-            ))?.to_string();
+            ))?;
+        let mbid = ids::Mbid::from_str(&mbid)?;
 
         println!("resolved MBID: {mbid}");
 
-        let acoust_response = acoustb.features(&mbid, "high-level")
-            .send()
-            .await?; 
-        assert!(acoust_response.status().is_success(), 
-            "acousticbrainz high-level status: {}", acoust_response.status());
-        let acoust_high: serde_json::Value = acoust_response.json().await?; 
-        println!("acousticbrainz.high-level:\n{}", 
-            serde_json::to_string_pretty(&acoust_high)?);
-
-        let acoust_response = acoustb.features(&mbid, "low-level")
-            .send()
-            .await?; 
-        assert!(acoust_response.status().is_success(), 
-            "acousticbrainz low-level status: {}", acoust_response.status());
-        let acoust_low: serde_json::Value = acoust_response.json().await?; 
-        println!("acousticbrainz.low-level:\n{}", 
-            serde_json::to_string_pretty(&acoust_low)?);
-
-        let lastfm_response = lastfm.track_top_tags_by_mbid(&mbid)
-            .send()
-            .await?; 
-        let mut tags: Option<serde_json::Value> = None; 
-        if lastfm_response.status().is_success() {
-            let v: serde_json::Value = lastfm_response.json().await?; 
-            if v.get("toptags").is_some() {
-                tags = Some(v);
-            }
-        }
+        let acoust_high = acoustb.high_level(&mbid).await?;
+        println!("acousticbrainz.high-level:\n{}",
+            serde_json::to_string_pretty(&acoust_high.0)?);
+
+        let acoust_low = acoustb.low_level(&mbid).await?;
+        println!("acousticbrainz.low-level:\n{}",
+            serde_json::to_string_pretty(&acoust_low.0)?);
+
+        let mut tags = lastfm.track_top_tags_by_mbid(mbid.as_str()).await
+            .ok()
+            .filter(|t| t.0.get("toptags").is_some());
 
         if tags.is_none() {
-            let lastfm_response = lastfm.track_top_tags(&first_artist, &track_title)
-                .send()
-                .await?; 
-            assert!(lastfm_response.status().is_success(),
-                "last.fm toptags: {}", lastfm_response.status());
-            tags = Some(lastfm_response.json().await?);
+            tags = Some(lastfm.track_top_tags(&first_artist, &track_title).await?);
         }
 
         let tags = tags.expect("no last.fm toptags found");
-        println!("lastfm.toptags:\n{}", serde_json::to_string_pretty(&tags)?);
-        assert!(tags.get("toptags").is_some(), 
+        println!("lastfm.toptags:\n{}", serde_json::to_string_pretty(&tags.0)?);
+        assert!(tags.0.get("toptags").is_some(),
             "expected toptags key in response");
 
         Ok(())
@@ -0,0 +1,216 @@
+//!
+//! src/metrics.rs  Andrew Belles  Sept 17th, 2025
+//!
+//! Optional Prometheus Pushgateway exporter. The crawler is a long-running
+//! daemon with no scrape endpoint of its own, so rather than exposing a
+//! `/metrics` route (which would need a scraper to come find it), a
+//! background task renders the text exposition format by hand and pushes it
+//! to a configured Pushgateway URL on a fixed interval. Gated off by default
+//! via `config::MetricsConfig`.
+//!
+//!
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Client;
+use tokio::sync::Semaphore;
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::config::MetricsConfig;
+use crate::errors::CrawlerError;
+use crate::persistent::{JobStatus, JobType, Persistent};
+use crate::status::{ClientsStats, PipelineCounters};
+
+/// Upper bound of each latency bucket, in seconds.
+const LATENCY_BUCKETS_SECS: [f64; 8] = [0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+/// Fixed-bucket histogram in the Prometheus exposition sense: each bucket
+/// counts observations `<= bound`, alongside a running sum and count.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS_SECS.len()],
+    sum_millis: AtomicU64,
+    count: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub fn observe(&self, elapsed: Duration) {
+        self.sum_millis.fetch_add(elapsed.as_millis() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+
+        let secs = elapsed.as_secs_f64();
+        for (bucket, bound) in self.buckets.iter().zip(LATENCY_BUCKETS_SECS) {
+            if secs <= bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn render(&self, name: &str, labels: &str, out: &mut String) {
+        for (bound, bucket) in LATENCY_BUCKETS_SECS.iter().zip(&self.buckets) {
+            let cumulative = bucket.load(Ordering::Relaxed);
+            out.push_str(&format!(
+                "{name}_bucket{{{labels},le=\"{bound}\"}} {cumulative}\n"
+            ));
+        }
+        let total = self.count.load(Ordering::Relaxed);
+        out.push_str(&format!("{name}_bucket{{{labels},le=\"+Inf\"}} {total}\n"));
+        out.push_str(&format!(
+            "{name}_sum{{{labels}}} {}\n",
+            self.sum_millis.load(Ordering::Relaxed) as f64 / 1000.0
+        ));
+        out.push_str(&format!("{name}_count{{{labels}}} {}\n", self.count.load(Ordering::Relaxed)));
+    }
+}
+
+/// Claimed/completed/failed counters and a latency histogram for one
+/// `JobType`, incremented from `crawler.rs`'s link/features loops.
+#[derive(Debug, Default)]
+pub struct JobTypeMetrics {
+    pub claimed: AtomicU64,
+    pub completed: AtomicU64,
+    pub failed: AtomicU64,
+    pub latency: LatencyHistogram,
+}
+
+/// All Prometheus metrics this crawler reports. One instance is shared
+/// across every worker loop via `Arc`.
+#[derive(Debug, Default)]
+pub struct CrawlerMetrics {
+    pub link: JobTypeMetrics,
+    pub features: JobTypeMetrics,
+    /// Delay handed to `Persistent::requeue_job` by `Crawler::handle_job_failure`
+    /// - distinct from `link`/`features`'s per-job duration histograms, this
+    /// tracks how long failed jobs are sitting out before their next attempt.
+    pub backoff: LatencyHistogram,
+}
+
+impl CrawlerMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Handles needed to read live gauges (semaphore permits, queue depth) at
+/// push time rather than snapshotting them once at startup.
+#[derive(Clone)]
+pub struct MetricsGauges {
+    pub musicbrainz_handler: Arc<Semaphore>,
+    pub musicbrainz_limit: usize,
+    pub features_handler: Arc<Semaphore>,
+    pub feature_limit: usize,
+}
+
+fn render_job_metrics(kind: &str, m: &JobTypeMetrics, out: &mut String) {
+    let labels = format!("kind=\"{kind}\"");
+    out.push_str(&format!("crawler_jobs_claimed_total{{{labels}}} {}\n", m.claimed.load(Ordering::Relaxed)));
+    out.push_str(&format!("crawler_jobs_completed_total{{{labels}}} {}\n", m.completed.load(Ordering::Relaxed)));
+    out.push_str(&format!("crawler_jobs_failed_total{{{labels}}} {}\n", m.failed.load(Ordering::Relaxed)));
+    m.latency.render("crawler_job_duration_seconds", &labels, out);
+}
+
+fn render_client_stats(name: &str, stats: &crate::status::ClientStats, out: &mut String) {
+    let labels = format!("service=\"{name}\"");
+    out.push_str(&format!("crawler_http_requests_total{{{labels}}} {}\n", stats.requests.load(Ordering::Relaxed)));
+    out.push_str(&format!("crawler_http_retries_total{{{labels}}} {}\n", stats.retries.load(Ordering::Relaxed)));
+    out.push_str(&format!("crawler_http_rate_limited_total{{{labels}}} {}\n", stats.rate_limited.load(Ordering::Relaxed)));
+}
+
+async fn render(
+    metrics: &CrawlerMetrics,
+    clients: &ClientsStats,
+    gauges: &MetricsGauges,
+    counters: &PipelineCounters,
+    db: &Persistent,
+) -> String {
+    let mut out = String::new();
+
+    out.push_str("# TYPE crawler_jobs_claimed_total counter\n");
+    out.push_str("# TYPE crawler_jobs_completed_total counter\n");
+    out.push_str("# TYPE crawler_jobs_failed_total counter\n");
+    out.push_str("# TYPE crawler_job_duration_seconds histogram\n");
+    render_job_metrics("link", &metrics.link, &mut out);
+    render_job_metrics("features", &metrics.features, &mut out);
+
+    out.push_str("# TYPE crawler_job_backoff_seconds histogram\n");
+    metrics.backoff.render("crawler_job_backoff_seconds", "kind=\"all\"", &mut out);
+
+    out.push_str("# TYPE crawler_tracks_discovered_total counter\n");
+    out.push_str(&format!(
+        "crawler_tracks_discovered_total {}\n",
+        counters.tracks_discovered.load(Ordering::Relaxed)
+    ));
+    out.push_str("# TYPE crawler_tracks_upserted_total counter\n");
+    out.push_str(&format!(
+        "crawler_tracks_upserted_total {}\n",
+        counters.tracks_upserted.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# TYPE crawler_http_requests_total counter\n");
+    out.push_str("# TYPE crawler_http_retries_total counter\n");
+    out.push_str("# TYPE crawler_http_rate_limited_total counter\n");
+    render_client_stats("spotify", &clients.spotify, &mut out);
+    render_client_stats("musicbrainz", &clients.musicbrainz, &mut out);
+    render_client_stats("acousticbrainz", &clients.acousticbrainz, &mut out);
+    render_client_stats("lastfm", &clients.lastfm, &mut out);
+    render_client_stats("acoustid", &clients.acoustid, &mut out);
+
+    out.push_str("# TYPE crawler_semaphore_permits_in_use gauge\n");
+    let mb_in_use = gauges.musicbrainz_limit
+        .saturating_sub(gauges.musicbrainz_handler.available_permits());
+    let feat_in_use = gauges.feature_limit
+        .saturating_sub(gauges.features_handler.available_permits());
+    out.push_str(&format!(
+        "crawler_semaphore_permits_in_use{{handler=\"musicbrainz\"}} {mb_in_use}\n"
+    ));
+    out.push_str(&format!(
+        "crawler_semaphore_permits_in_use{{handler=\"features\"}} {feat_in_use}\n"
+    ));
+
+    out.push_str("# TYPE crawler_queue_depth gauge\n");
+    for (kind, label) in [(JobType::Link, "link"), (JobType::Features, "features")] {
+        match db.count_jobs(kind, JobStatus::Pending).await {
+            Ok(depth) => out.push_str(&format!(
+                "crawler_queue_depth{{kind=\"{label}\"}} {depth}\n"
+            )),
+            Err(e) => warn!(error = ?e, kind = label, "metrics.queue_depth failed"),
+        }
+    }
+
+    out
+}
+
+/// Pushes the rendered metrics to `{pushgateway_url}/metrics/job/track-crawler`
+/// every `push_interval` until `shutdown` is cancelled. A failed push is
+/// logged and retried on the next tick rather than treated as fatal - metrics
+/// are an observability aid, not a critical path.
+pub async fn push_loop(
+    cfg: MetricsConfig,
+    metrics: Arc<CrawlerMetrics>,
+    clients: ClientsStats,
+    gauges: MetricsGauges,
+    counters: Arc<PipelineCounters>,
+    db: Arc<Persistent>,
+    shutdown: CancellationToken,
+) -> Result<(), CrawlerError> {
+    let client = Client::new();
+    let url = format!("{}/metrics/job/track-crawler", cfg.pushgateway_url.trim_end_matches('/'));
+
+    loop {
+        tokio::select! {
+            () = shutdown.cancelled() => break,
+            () = sleep(cfg.push_interval) => {}
+        }
+
+        let body = render(&metrics, &clients, &gauges, &counters, &db).await;
+        if let Err(e) = client.put(&url).body(body).send().await {
+            warn!(error = ?e, %url, "metrics.push failed");
+        }
+    }
+
+    Ok(())
+}
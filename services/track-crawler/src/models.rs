@@ -0,0 +1,142 @@
+//!
+//! src/models.rs  Andrew Belles  Sept 14th, 2025
+//!
+//! Typed response shapes for each `fetch` client. Deserializing straight into
+//! these instead of walking raw `serde_json::Value` with `.pointer()`/`.get()`
+//! gives compile-time field names and turns a missing field into a parse
+//! error instead of a silently empty default somewhere downstream.
+//!
+//!
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyArtist {
+    pub id: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyAlbum {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub release_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpotifyExternalIds {
+    pub isrc: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyTrack {
+    pub id: String,
+    pub name: String,
+    pub duration_ms: Option<i64>,
+    pub explicit: Option<bool>,
+    pub popularity: Option<i64>,
+    pub album: Option<SpotifyAlbum>,
+    #[serde(default)]
+    pub artists: Vec<SpotifyArtist>,
+    #[serde(default)]
+    pub external_ids: SpotifyExternalIds,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpotifyTokenResponse {
+    pub access_token: String,
+    pub expires_in: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpotifyTracksResponse {
+    pub tracks: Vec<Option<SpotifyTrack>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpotifySearchPage {
+    #[serde(default)]
+    pub items: Vec<SpotifyTrack>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpotifySearchResponse {
+    pub tracks: SpotifySearchPage,
+}
+
+/// Minimal item shape for paginated seed endpoints — seeding only needs the
+/// bare track ID to hand off to `SpotifyClient::batch_track`, not the full
+/// track object each page item otherwise carries.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpotifyTrackRef {
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpotifyPlaylistTrackItem {
+    #[serde(default)]
+    pub track: Option<SpotifyTrackRef>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpotifyPlaylistTracksPage {
+    #[serde(default)]
+    pub items: Vec<SpotifyPlaylistTrackItem>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpotifyAlbumTracksPage {
+    #[serde(default)]
+    pub items: Vec<SpotifyTrackRef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MbRecording {
+    pub id: String,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MbIsrcLookup {
+    #[serde(default)]
+    pub recordings: Vec<MbRecording>,
+}
+
+/// AcousticBrainz keys its `highlevel`/`lowlevel` feature trees by
+/// classifier/algorithm name, which varies per model version, so there's no
+/// fixed set of fields to name here. `sink::extract_high_level` /
+/// `extract_low_level` already walk the tree generically; this wrapper just
+/// gives the client method a typed return value instead of a bare `Response`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct AcousticHighLevel(pub serde_json::Value);
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct AcousticLowLevel(pub serde_json::Value);
+
+/// Same rationale as `AcousticHighLevel`: last.fm's `toptags` array length
+/// and tag vocabulary are both unbounded, and `sink::write_json` stores the
+/// response verbatim, so there's nothing to gain from naming every field.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct LastFmTopTags(pub serde_json::Value);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AcoustIdRecording {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AcoustIdResult {
+    pub id: String,
+    pub score: f64,
+    #[serde(default)]
+    pub recordings: Vec<AcoustIdRecording>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AcoustIdLookup {
+    #[serde(default)]
+    pub results: Vec<AcoustIdResult>,
+}
@@ -6,42 +6,46 @@
 //! and raw json for tracks (methods defined in src/sink.rs)
 //!
 
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use sqlx::{sqlite::SqlitePoolOptions, sqlite::SqliteConnectOptions, Pool, Row, Sqlite};
-use uuid::Uuid; 
+use uuid::Uuid;
 use crate::errors::CrawlerError;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SpotifyTrack {
-    pub spotify_id: Option<String>, 
-    pub isrc: Option<String>, 
-    pub title: String, 
-    pub artist_all: Vec<String>, 
-    pub album: Option<String>, 
-    pub duration_ms: Option<i64>, 
-    pub release_date: Option<String>, 
-    pub explicit: Option<bool>, 
-    pub popularity: Option<i32> 
+    pub spotify_id: Option<String>,
+    pub isrc: Option<String>,
+    pub title: String,
+    pub artist_all: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub release_date: Option<String>,
+    pub explicit: Option<bool>,
+    pub popularity: Option<i32>,
+    /// Chromaprint fingerprint for the `acoustid` MBID-resolution fallback
+    /// (see `crawler::resolve_mbid`). Spotify's metadata API never returns
+    /// one, so this is always `None` coming out of `SpotifyTrack::new` today
+    /// - it exists so a future ingest path with real audio access (or a
+    /// locally-run `fpcalc`) has somewhere to put it.
+    pub fingerprint: Option<String>
 }
 
 impl SpotifyTrack {
-    pub fn new(track: &serde_json::Value) -> Self {
+    pub fn new(track: &crate::models::SpotifyTrack) -> Self {
         Self {
-            spotify_id: track.get("id").and_then(|v| v.as_str()).map(|s| s.to_string()),
-            isrc: track.pointer("/external_ids/isrc").and_then(|v| v.as_str()).map(str::to_string),
-            title: track.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-            artist_all: track.get("artists").and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter().filter_map(|a| a.get("name").and_then(|v| v.as_str()))
-                       .map(|name| name.to_string()).collect()
-                }).unwrap_or_else(|| Vec::new()),
-            album: track.pointer("/album/name").and_then(|v| v.as_str()).map(str::to_string),
-            duration_ms: track.get("duration_ms").and_then(|v| v.as_i64()),
-            release_date: track.pointer("/album/release_date").and_then(|v| v.as_str()).map(str::to_string),
-            explicit: track.get("explicit").and_then(|v| v.as_bool()),
-            popularity: track.get("popularity").and_then(|v| v.as_i64()).map(|x| x as i32),
+            spotify_id: Some(track.id.clone()),
+            isrc: track.external_ids.isrc.clone(),
+            title: track.name.clone(),
+            artist_all: track.artists.iter().map(|a| a.name.clone()).collect(),
+            album: track.album.as_ref().and_then(|a| a.name.clone()),
+            duration_ms: track.duration_ms,
+            release_date: track.album.as_ref().and_then(|a| a.release_date.clone()),
+            explicit: track.explicit,
+            popularity: track.popularity.map(|x| x as i32),
+            fingerprint: None,
         }
     }
 }
@@ -68,12 +72,83 @@ impl JobType {
     }
 }
 
+/// Which resolution strategy `crawler::resolve_mbid` used to land an MBID,
+/// recorded on `tracks.link_resolved_via` so fallback hit rates can be
+/// measured across the crawl instead of only knowing link succeeded or not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkTier {
+    Isrc,
+    Text,
+    AcoustId
+}
+
+impl LinkTier {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LinkTier::Isrc => "isrc",
+            LinkTier::Text => "text",
+            LinkTier::AcoustId => "acoustid"
+        }
+    }
+}
+
+/// Which kind of seed walk a `seed_cursors` row tracks progress for.
+/// `Feed` covers `feed_loop`'s exhaustive year-bucket sweep, keyed by query
+/// string (e.g. `"year:1975"`) rather than a Spotify playlist/album id, plus
+/// a synthetic `"__year__"` row tracking which year bucket is current.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedKind {
+    Playlist,
+    Album,
+    Feed
+}
+
+impl SeedKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SeedKind::Playlist => "playlist",
+            SeedKind::Album => "album",
+            SeedKind::Feed => "feed"
+        }
+    }
+}
+
+/// Lets a freshly-discovered track jump a large `Background` backlog
+/// (e.g. the bulk catch-up `requeue_incomplete_tracks` enqueues on resume)
+/// instead of queuing strictly FIFO. `claim_one_job` always drains every
+/// pending `Foreground` job before touching a `Background` one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobPriority {
+    Foreground,
+    Background
+}
+
+impl JobPriority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobPriority::Foreground => "foreground",
+            JobPriority::Background => "background"
+        }
+    }
+    pub fn parse(s: &str) -> Option<JobPriority> {
+        match s {
+            "foreground" => Some(JobPriority::Foreground),
+            "background" => Some(JobPriority::Background),
+            _ => None
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JobStatus {
-    Pending, 
-    Active, 
-    Done, 
-    Failed
+    Pending,
+    Active,
+    Done,
+    Failed,
+    /// Terminal, same as `Failed` but reserved for jobs `dead_letter_job`
+    /// gave up on - lets `count_jobs` report the dead-letter backlog size
+    /// straight off `jobs.status` instead of a separate `dead_letter` count.
+    Dead
 }
 
 impl JobStatus {
@@ -82,7 +157,8 @@ impl JobStatus {
             JobStatus::Pending => "pending",
             JobStatus::Active  => "active",
             JobStatus::Done    => "done",
-            JobStatus::Failed  => "failed"
+            JobStatus::Failed  => "failed",
+            JobStatus::Dead    => "dead"
         }
     }
     pub fn parse(s: &str) -> Option<JobStatus> {
@@ -91,7 +167,8 @@ impl JobStatus {
             "active"  => Some(JobStatus::Active),
             "done"    => Some(JobStatus::Done),
             "failed"  => Some(JobStatus::Failed),
-            _ => None 
+            "dead"    => Some(JobStatus::Dead),
+            _ => None
         }
     }
 }
@@ -99,22 +176,29 @@ impl JobStatus {
 #[derive(Debug, Clone)]
 pub struct Job {
     pub job_id: i64,
-    pub track_id: String, 
+    pub track_id: String,
     pub kind: JobType,
-    pub attempt: i64
+    pub priority: JobPriority,
+    pub attempt: i64,
+    /// Per-job override for how many attempts `handle_job_failure` allows
+    /// before dead-lettering. `None` falls back to the crawl-wide
+    /// `CrawlerLimits::job_max_attempts`.
+    pub max_attempts: Option<i64>
 }
 
 #[derive(Debug, Clone)]
 pub struct Track {
-    pub id: String, 
-    pub title: Option<String>, 
-    pub spotify_id: Option<String>, 
+    pub id: String,
+    pub title: Option<String>,
+    pub spotify_id: Option<String>,
     pub artist_all: Vec<String>,
-    pub isrc: Option<String>, 
-    pub mb_recording_id: Option<String>, 
-    pub linked_ok: bool, 
+    pub isrc: Option<String>,
+    pub mb_recording_id: Option<String>,
+    pub linked_ok: bool,
     pub features_ok: bool,
-    pub updated_at: i64 
+    pub duration_ms: Option<i64>,
+    pub fingerprint: Option<String>,
+    pub updated_at: i64
 }
 
 impl Track {
@@ -126,128 +210,299 @@ impl Track {
     }
 }
 
+/// Model-ready, row-major dense matrix built from the sparse `features`
+/// table by `Persistent::export_feature_matrix`. `values[r * columns.len() + c]`
+/// is `track_ids[r]`'s z-score-normalized value for `columns[c]`.
+#[derive(Debug, Clone)]
+pub struct FeatureMatrix {
+    pub track_ids: Vec<String>,
+    pub columns: Vec<(String, String)>,
+    pub values: Vec<f64>,
+}
+
+/// Lowercases, collapses whitespace, and pads `"{title} {artist}"` with two
+/// spaces on each side so the leading/trailing characters form trigrams too.
+fn trigram_key(title: &str, artist: &str) -> String {
+    let collapsed = format!("{title} {artist}")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    format!("  {collapsed}  ")
+}
+
+/// All length-3 substrings of `key`, deduplicated into a set - a `key`
+/// shorter than 3 chars becomes a single trigram covering the whole thing.
+fn trigrams(key: &str) -> HashSet<String> {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() < 3 {
+        return HashSet::from([chars.into_iter().collect()]);
+    }
+    (0..=chars.len() - 3)
+        .map(|i| chars[i..i + 3].iter().collect::<String>())
+        .collect()
+}
+
+/// Jaccard similarity `|A ∩ B| / |A ∪ B|` over two trigram sets.
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// How `Persistent::init` gets its pool(s). `Fresh` is the normal
+/// standalone-crawler path: connect to `database_url` and split it into the
+/// usual read/write pools. `Existing` is for a host application that already
+/// owns a `Pool<Sqlite>` (e.g. it runs this store alongside its own tables)
+/// and wants `Persistent` to just run `ensure_schema` against it rather than
+/// open a second connection.
+pub enum ConnectionOptions {
+    Fresh {
+        database_url: String,
+        /// Passed straight to `SqliteConnectOptions::disable_statement_logging`;
+        /// sqlx logs every statement at INFO by default, which drowns out a
+        /// host app's own logging when this store is embedded.
+        disable_statement_logging: bool,
+        /// `read_pool`'s `max_connections`. `write_pool` is always capped at
+        /// 1 - see its field doc comment.
+        max_connections: u32,
+    },
+    Existing(Pool<Sqlite>),
+}
+
 pub struct Persistent {
-    pool: Pool<Sqlite>
+    /// Multi-connection pool for read-only queries. WAL lets these run
+    /// concurrently with `write_pool`'s single writer.
+    read_pool: Pool<Sqlite>,
+    /// Single-connection pool every mutating query and transaction routes
+    /// through, so writes serialize in-process instead of contending for
+    /// SQLite's one file-level write lock and surfacing as `SQLITE_BUSY`.
+    write_pool: Pool<Sqlite>,
 }
 
 impl Persistent {
 
-    async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<(), CrawlerError> {
-        // ensure that schema exists  
-        sqlx::query(
-            r"
+    /// Ordered, append-only list of schema migrations. Each entry's SQL runs
+    /// once, in its own transaction, the first time a DB's
+    /// `schema_migrations` table reports a lower max version; never edit a
+    /// migration already released; add a new one instead. Version 1 is the
+    /// schema as of the migration subsystem's introduction, so a pre-existing
+    /// database upgrades transparently (every `CREATE TABLE`/`INDEX` here is
+    /// already `IF NOT EXISTS`).
+    const MIGRATIONS: &'static [(i64, &'static str)] = &[
+        (1, r"
             CREATE TABLE IF NOT EXISTS tracks (
-              id                TEXT PRIMARY KEY,           
+              id                TEXT PRIMARY KEY,
               spotify_id        TEXT UNIQUE,
               isrc              TEXT UNIQUE,
               mb_recording_id   TEXT UNIQUE,
               title             TEXT,
-              artist_all        TEXT,                       
+              artist_all        TEXT,
               album             TEXT,
               duration_ms       INTEGER,
               release_date      TEXT,
-              explicit          INTEGER,                    
+              explicit          INTEGER,
               popularity        INTEGER,
+              fingerprint       TEXT,
+              link_resolved_via TEXT CHECK (link_resolved_via IN ('isrc','text','acoustid')),
               linked_ok         INTEGER NOT NULL DEFAULT 0,
-              features_ok       INTEGER NOT NULL DEFAULT 0, 
+              features_ok       INTEGER NOT NULL DEFAULT 0,
               created_at        INTEGER NOT NULL,
               updated_at        INTEGER NOT NULL
             );
-            "
-        ).execute(pool).await?; 
 
-        sqlx::query(
-            r"
             CREATE TABLE IF NOT EXISTS jobs (
               job_id      INTEGER PRIMARY KEY AUTOINCREMENT,
               track_id    TEXT NOT NULL,
               kind        TEXT NOT NULL CHECK (kind IN ('link','features')),
               status      TEXT NOT NULL CHECK (status IN (
                   'pending','active',
-                  'done','failed')
+                  'done','failed','dead')
                   ) DEFAULT 'pending',
+              priority    TEXT NOT NULL CHECK (priority IN (
+                  'foreground','background')
+                  ) DEFAULT 'background',
               attempt     INTEGER NOT NULL DEFAULT 0,
+              next_visible_at INTEGER NOT NULL DEFAULT 0,
+              lease_expires_at INTEGER,
               last_error  TEXT,
               created_at  INTEGER NOT NULL,
               updated_at  INTEGER NOT NULL,
               UNIQUE(track_id, kind),
               FOREIGN KEY(track_id) REFERENCES tracks(id) ON DELETE CASCADE
             );
-            "
-        ).execute(pool).await?; 
 
-        sqlx::query(
-        r"
-        CREATE TABLE IF NOT EXISTS raw_files (
-          id          INTEGER PRIMARY KEY AUTOINCREMENT,
-          track_id    TEXT NOT NULL,
-          source      TEXT NOT NULL,
-          subtype     TEXT NOT NULL,
-          key         TEXT NOT NULL,
-          rel_path    TEXT NOT NULL,
-          created_at  INTEGER NOT NULL,
-          UNIQUE (source, subtype, key)
-        );"
-        ).execute(pool).await?;
+            CREATE TABLE IF NOT EXISTS dead_letter (
+              id          INTEGER PRIMARY KEY AUTOINCREMENT,
+              track_id    TEXT NOT NULL,
+              kind        TEXT NOT NULL CHECK (kind IN ('link','features')),
+              attempt     INTEGER NOT NULL,
+              last_error  TEXT NOT NULL,
+              created_at  INTEGER NOT NULL
+            );
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_raw_files_track ON raw_files(
-                track_id);"
-        ).execute(pool).await?;
+            CREATE INDEX IF NOT EXISTS idx_dead_letter_track ON dead_letter(track_id);
 
-        sqlx::query(
-        r"
-        CREATE TABLE IF NOT EXISTS features (
-          track_id    TEXT NOT NULL,
-          source      TEXT NOT NULL,
-          feature     TEXT NOT NULL,
-          dtype       TEXT NOT NULL CHECK (dtype IN ('num','text')),
-          num_value   REAL,
-          text_value  TEXT,
-          updated_at  INTEGER NOT NULL,
-          PRIMARY KEY (track_id, source, feature)
-        );"
-        ).execute(pool).await?;
+            CREATE TABLE IF NOT EXISTS raw_files (
+              id          INTEGER PRIMARY KEY AUTOINCREMENT,
+              track_id    TEXT NOT NULL,
+              source      TEXT NOT NULL,
+              subtype     TEXT NOT NULL,
+              key         TEXT NOT NULL,
+              rel_path    TEXT NOT NULL,
+              created_at  INTEGER NOT NULL,
+              UNIQUE (source, subtype, key)
+            );
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_features_track ON features(track_id);")
-            .execute(pool).await?;
+            CREATE INDEX IF NOT EXISTS idx_raw_files_track ON raw_files(track_id);
 
-        sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_jobs_pending ON jobs(kind, status);"
-        ).execute(pool).await?;
+            CREATE TABLE IF NOT EXISTS features (
+              track_id    TEXT NOT NULL,
+              source      TEXT NOT NULL,
+              feature     TEXT NOT NULL,
+              dtype       TEXT NOT NULL CHECK (dtype IN ('num','text')),
+              num_value   REAL,
+              text_value  TEXT,
+              updated_at  INTEGER NOT NULL,
+              PRIMARY KEY (track_id, source, feature)
+            );
 
+            CREATE INDEX IF NOT EXISTS idx_features_track ON features(track_id);
+
+            CREATE INDEX IF NOT EXISTS idx_jobs_pending ON jobs(kind, status, priority, created_at);
+
+            CREATE INDEX IF NOT EXISTS idx_jobs_lease ON jobs(status, lease_expires_at);
+
+            CREATE TABLE IF NOT EXISTS seed_cursors (
+              kind          TEXT NOT NULL CHECK (kind IN ('playlist','album','feed')),
+              seed_id       TEXT NOT NULL,
+              cursor_offset INTEGER NOT NULL DEFAULT 0,
+              updated_at    INTEGER NOT NULL,
+              PRIMARY KEY (kind, seed_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_tracks_spotify ON tracks(spotify_id);
+
+            CREATE INDEX IF NOT EXISTS idx_tracks_mbid ON tracks(mb_recording_id);
+        "),
+        (2, r"
+            CREATE TABLE IF NOT EXISTS track_trigrams (
+              track_id  TEXT NOT NULL,
+              trigram   TEXT NOT NULL,
+              FOREIGN KEY(track_id) REFERENCES tracks(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_track_trigrams_trigram ON track_trigrams(trigram);
+            CREATE INDEX IF NOT EXISTS idx_track_trigrams_track ON track_trigrams(track_id);
+        "),
+        (3, r"
+            ALTER TABLE jobs ADD COLUMN max_attempts INTEGER;
+        "),
+    ];
+
+    /// Brings the DB up to `Self::MIGRATIONS`'s latest version, applying
+    /// each pending one in its own transaction and recording it in
+    /// `schema_migrations` before moving to the next. A migration failing
+    /// mid-way rolls back and returns `Err`, leaving the DB at its last
+    /// good version instead of silently limping on with a half-applied one.
+    async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<(), CrawlerError> {
         sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_tracks_spotify ON tracks(spotify_id);"
+            r"
+            CREATE TABLE IF NOT EXISTS schema_migrations (
+              version     INTEGER PRIMARY KEY,
+              applied_at  INTEGER NOT NULL
+            );
+            "
         ).execute(pool).await?;
 
-        sqlx::query(
-            "CREATE INDEX IF NOT EXISTS idx_tracks_mbid ON tracks(mb_recording_id);"
-        ).execute(pool).await?; 
+        let current: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations;"
+        )
+        .fetch_one(pool)
+        .await?;
 
-        Ok(())
-    } 
+        for &(version, sql) in Self::MIGRATIONS {
+            if version <= current {
+                continue;
+            }
+
+            let mut tx = pool.begin().await?;
 
-    pub async fn init(database_url: &str) -> Result<Self, CrawlerError> {
-        let is_memory = database_url == "sqlite::memory:";
+            sqlx::raw_sql(sql).execute(&mut *tx).await.map_err(|e|
+                CrawlerError::Db(format!("migration {version} failed: {e}"))
+            )?;
 
-        let mut opts = SqliteConnectOptions::from_str(database_url)?
-            .create_if_missing(true);
+            sqlx::query(
+                "INSERT INTO schema_migrations (version, applied_at) VALUES (?1, ?2);"
+            )
+            .bind(version)
+            .bind(Self::now())
+            .execute(&mut *tx)
+            .await?;
 
-        // WAL is file-only; donâ€™t set it for in-memory
-        if !is_memory {
-            opts = opts.journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
-                       .synchronous(sqlx::sqlite::SqliteSynchronous::Normal);
+            tx.commit().await?;
         }
 
-        let pool = SqlitePoolOptions::new()
-            .min_connections(1)
-            .max_connections(if is_memory {1} else {8})
-            .connect_with(opts)
-            .await?;
+        Ok(())
+    }
+
+    /// How long a connection waits on `SQLITE_BUSY` before giving up,
+    /// covering any residual contention between `read_pool` and
+    /// `write_pool` instead of failing a query outright.
+    const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub async fn init(options: ConnectionOptions) -> Result<Self, CrawlerError> {
+        let (read_pool, write_pool) = match options {
+            ConnectionOptions::Fresh { database_url, disable_statement_logging, max_connections } => {
+                let is_memory = database_url == "sqlite::memory:";
+
+                let mut opts = SqliteConnectOptions::from_str(&database_url)?
+                    .create_if_missing(true)
+                    .busy_timeout(Self::BUSY_TIMEOUT);
+
+                if disable_statement_logging {
+                    opts = opts.disable_statement_logging();
+                }
+
+                // WAL is file-only; don't set it for in-memory
+                if !is_memory {
+                    opts = opts.journal_mode(sqlx::sqlite::SqliteJournalMode::Wal)
+                               .synchronous(sqlx::sqlite::SqliteSynchronous::Normal);
+                }
+
+                // A single writer connection serializes every mutating query
+                // in-process, so they queue up here rather than contending for
+                // SQLite's one file-level write lock.
+                let write_pool = SqlitePoolOptions::new()
+                    .min_connections(1)
+                    .max_connections(1)
+                    .connect_with(opts.clone())
+                    .await?;
+
+                // In-memory databases are private per-connection, so a second pool
+                // would just see an empty DB; share the single writer connection
+                // instead of splitting it.
+                let read_pool = if is_memory {
+                    write_pool.clone()
+                } else {
+                    SqlitePoolOptions::new()
+                        .min_connections(1)
+                        .max_connections(max_connections)
+                        .connect_with(opts)
+                        .await?
+                };
+
+                (read_pool, write_pool)
+            }
+            ConnectionOptions::Existing(pool) => (pool.clone(), pool),
+        };
 
         // Always create schema right away
-        Self::ensure_schema(&pool).await?;
+        Self::ensure_schema(&write_pool).await?;
 
-        Ok(Self { pool })
+        Ok(Self { read_pool, write_pool })
     }
 
 
@@ -262,8 +517,8 @@ impl Persistent {
         )
         .bind(kind.as_str())
         .bind(status.as_str())
-        .fetch_one(&self.pool)
-        .await?; 
+        .fetch_one(&self.read_pool)
+        .await?;
         Ok(count)
     }
 
@@ -286,8 +541,9 @@ impl Persistent {
                        release_date = COALESCE(?5, release_date),
                        explicit = COALESCE(?6, explicit),
                        popularity = COALESCE(?7, popularity),
-                       updated_at = ?8
-                 WHERE id = ?9;
+                       fingerprint = COALESCE(?8, fingerprint),
+                       updated_at = ?9
+                 WHERE id = ?10;
                 "
             )
             .bind(Some(&track.title))
@@ -297,10 +553,11 @@ impl Persistent {
             .bind(track.release_date.clone())
             .bind(track.explicit.map(i32::from))
             .bind(track.popularity)
+            .bind(track.fingerprint.as_ref())
             .bind(Self::now())
             .bind(&existing)
-            .execute(&self.pool)
-            .await?; 
+            .execute(&self.write_pool)
+            .await?;
 
             if let Some(isrc) = &track.isrc {
                 let _ = sqlx::query(
@@ -308,9 +565,11 @@ impl Persistent {
                 )
                 .bind(isrc)
                 .bind(&existing)
-                .execute(&self.pool)
-                .await; 
+                .execute(&self.write_pool)
+                .await;
             }
+            self.index_trigrams(&existing, &track.title, track.artist_all.first()
+                .map(String::as_str).unwrap_or("")).await?;
             return Ok((existing, false));
         }
 
@@ -318,10 +577,10 @@ impl Persistent {
         sqlx::query(
             r"
             INSERT INTO tracks (
-                id, spotify_id, isrc, title, artist_all, album, duration_ms, 
-                release_date, explicit, popularity, linked_ok, 
+                id, spotify_id, isrc, title, artist_all, album, duration_ms,
+                release_date, explicit, popularity, fingerprint, linked_ok,
                 features_ok, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, 0, 0, ?11, ?11);
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 0, 0, ?12, ?12);
             "
         )
         .bind(&id)
@@ -334,31 +593,127 @@ impl Persistent {
         .bind(track.release_date.as_ref())
         .bind(track.explicit.map(i32::from))
         .bind(track.popularity)
+        .bind(track.fingerprint.as_ref())
         .bind(Self::now())
-        .execute(&self.pool)
-        .await?; 
+        .execute(&self.write_pool)
+        .await?;
+
+        self.index_trigrams(&id, &track.title, track.artist_all.first()
+            .map(String::as_str).unwrap_or("")).await?;
 
         Ok((id, true))
     }
 
-    pub async fn get_track_id(&self, spotify_id: &str) -> 
+    /// Replaces `track_id`'s `track_trigrams` rows with the trigram set of
+    /// `"{title} {artist}"`, kept in sync with `tracks` on every
+    /// `upsert_track` so `find_similar_tracks`'s pre-filter stays accurate.
+    async fn index_trigrams(&self, track_id: &str, title: &str, artist: &str) ->
+        Result<(), CrawlerError> {
+        sqlx::query("DELETE FROM track_trigrams WHERE track_id = ?1;")
+            .bind(track_id)
+            .execute(&self.write_pool)
+            .await?;
+
+        let key = trigram_key(title, artist);
+        for trigram in trigrams(&key) {
+            sqlx::query("INSERT INTO track_trigrams (track_id, trigram) VALUES (?1, ?2);")
+                .bind(track_id)
+                .bind(trigram)
+                .execute(&self.write_pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Finds tracks whose `"{title} {artist}"` trigram set has a Jaccard
+    /// similarity of at least `threshold` to the query's, sorted by score
+    /// descending, capped at `limit`. Cheap pre-filter: only tracks sharing
+    /// at least one trigram with the query are pulled from the DB at all;
+    /// the actual Jaccard similarity is computed over the full sets in Rust.
+    pub async fn find_similar_tracks(
+        &self,
+        title: &str,
+        artist: &str,
+        threshold: f64,
+        limit: usize,
+    ) -> Result<Vec<(String, f64)>, CrawlerError> {
+        let query_trigrams = trigrams(&trigram_key(title, artist));
+        if query_trigrams.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut candidates_qb = sqlx::QueryBuilder::new(
+            "SELECT DISTINCT track_id FROM track_trigrams WHERE trigram IN ("
+        );
+        {
+            let mut sep = candidates_qb.separated(", ");
+            for trigram in &query_trigrams {
+                sep.push_bind(trigram);
+            }
+        }
+        candidates_qb.push(");");
+
+        let candidate_ids: Vec<String> = candidates_qb.build()
+            .fetch_all(&self.read_pool)
+            .await?
+            .into_iter()
+            .map(|row| row.get::<String, _>("track_id"))
+            .collect();
+
+        if candidate_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut sets_qb = sqlx::QueryBuilder::new(
+            "SELECT track_id, trigram FROM track_trigrams WHERE track_id IN ("
+        );
+        {
+            let mut sep = sets_qb.separated(", ");
+            for id in &candidate_ids {
+                sep.push_bind(id);
+            }
+        }
+        sets_qb.push(");");
+
+        let mut candidate_trigrams: HashMap<String, HashSet<String>> = HashMap::new();
+        for row in sets_qb.build().fetch_all(&self.read_pool).await? {
+            let track_id: String = row.get("track_id");
+            let trigram: String = row.get("trigram");
+            candidate_trigrams.entry(track_id).or_default().insert(trigram);
+        }
+
+        let mut scored: Vec<(String, f64)> = candidate_trigrams
+            .into_iter()
+            .map(|(track_id, set)| (track_id, jaccard(&query_trigrams, &set)))
+            .filter(|(_, score)| *score >= threshold)
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+
+        Ok(scored)
+    }
+
+    pub async fn get_track_id(&self, spotify_id: &str) ->
         Result<Option<String>, CrawlerError> {
         let row = sqlx::query("SELECT id FROM tracks WHERE spotify_id = ?1 LIMIT 1;")
             .bind(spotify_id)
-            .fetch_optional(&self.pool)
-            .await?; 
+            .fetch_optional(&self.read_pool)
+            .await?;
         Ok( row.map(|r| r.get::<String, _>("id")))
     }
 
-    pub async fn set_mbid(&self, track_id: &str, mbid: &str) -> Result<(), CrawlerError> {
+    pub async fn set_mbid(&self, track_id: &str, mbid: &str, tier: LinkTier) -> Result<(), CrawlerError> {
         sqlx::query(
-            "UPDATE tracks SET mb_recording_id = ?1, linked_ok = 1, updated_at = ?2 WHERE id = ?3"
+            "UPDATE tracks SET mb_recording_id = ?1, link_resolved_via = ?2, linked_ok = 1, updated_at = ?3 WHERE id = ?4"
         )
         .bind(mbid)
+        .bind(tier.as_str())
         .bind(Self::now())
         .bind(track_id)
-        .execute(&self.pool)
-        .await?; 
+        .execute(&self.write_pool)
+        .await?;
         Ok(())
     }
 
@@ -368,67 +723,107 @@ impl Persistent {
         )
         .bind(Self::now())
         .bind(track_id)
-        .execute(&self.pool)
-        .await?; 
+        .execute(&self.write_pool)
+        .await?;
         Ok(())
-    } 
+    }
 
-    pub async fn enqueue_job_if_missing(&self, track_id: &str, kind: JobType) ->
-        Result<(), CrawlerError> {
+    /// `max_attempts` overrides `CrawlerLimits::job_max_attempts` for this
+    /// job alone (e.g. a seed known to be flaky); pass `None` to fall back
+    /// to the crawl-wide default.
+    pub async fn enqueue_job_if_missing(
+        &self, track_id: &str, kind: JobType, priority: JobPriority,
+        max_attempts: Option<i64>
+    ) -> Result<(), CrawlerError> {
         sqlx::query(
             r"
             INSERT OR IGNORE INTO jobs (
-            track_id, kind, status, 
-            attempt, created_at, updated_at
+            track_id, kind, status, priority,
+            attempt, max_attempts, created_at, updated_at
             )
-            VALUES (?1, ?2, 'pending', 0, ?3, ?3);
+            VALUES (?1, ?2, 'pending', ?3, 0, ?4, ?5, ?5);
             "
         )
         .bind(track_id)
         .bind(kind.as_str())
+        .bind(priority.as_str())
+        .bind(max_attempts)
         .bind(Self::now())
-        .execute(&self.pool)
-        .await?; 
+        .execute(&self.write_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Bumps `track_id`'s pending `kind` job to `Foreground`, if one exists,
+    /// so `claim_one_job` drains it ahead of the background backlog. Used by
+    /// `status::submit_foreground` to expedite a specific track on request.
+    pub async fn bump_priority(&self, track_id: &str, kind: JobType) -> Result<(), CrawlerError> {
+        sqlx::query(
+            r"
+            UPDATE jobs SET priority = 'foreground', updated_at = ?1
+                WHERE track_id = ?2 AND kind = ?3 AND status = 'pending';
+            "
+        )
+        .bind(Self::now())
+        .bind(track_id)
+        .bind(kind.as_str())
+        .execute(&self.write_pool)
+        .await?;
         Ok(())
     }
 
-    pub async fn claim_one_job(&self, kind: JobType) -> 
+    /// Claims the oldest `Foreground` job of `kind` if one is pending,
+    /// falling back to the oldest `Background` job otherwise - a saturated
+    /// background backlog can never starve a foreground job. The claim
+    /// itself is a transactional read-then-conditional-update (sqlite has no
+    /// `SELECT ... FOR UPDATE SKIP LOCKED`; its serialized writers give the
+    /// same "exactly one worker wins the row" guarantee). Sets
+    /// `lease_expires_at = now + lease_secs`; a worker that dies mid-job
+    /// leaves it to `reclaim_expired_jobs` once the lease lapses.
+    pub async fn claim_one_job(&self, kind: JobType, lease_secs: i64) ->
         Result<Option<Job>, CrawlerError> {
-        let mut tx = self.pool.begin().await?; 
+        let mut tx = self.write_pool.begin().await?;
+
+        let now = Self::now();
 
         let row = sqlx::query(
             r"
-            SELECT job_id, track_id, kind, attempt 
-              FROM jobs 
-            WHERE kind = ?1 AND status = 'pending'
-            ORDER BY created_at ASC 
+            SELECT job_id, track_id, kind, priority, attempt, max_attempts
+              FROM jobs
+            WHERE kind = ?1 AND status = 'pending' AND next_visible_at <= ?2
+            ORDER BY (CASE priority WHEN 'foreground' THEN 0 ELSE 1 END) ASC,
+                     created_at ASC
             LIMIT 1;
             "
         )
         .bind(kind.as_str())
+        .bind(now)
         .fetch_optional(&mut *tx)
-        .await?; 
+        .await?;
 
         let Some(row) = row else {
-            tx.rollback().await?; 
+            tx.rollback().await?;
             return Ok(None);
         };
 
-        let job_id   = row.get::<i64, _>("job_id");
-        let track_id = row.get::<String, _>("track_id");
-        let kind     = row.get::<String, _>("kind");
-        let attempt  = row.get::<i64, _>("attempt");
-        let now      = Self::now();
+        let job_id       = row.get::<i64, _>("job_id");
+        let track_id     = row.get::<String, _>("track_id");
+        let kind         = row.get::<String, _>("kind");
+        let priority     = row.get::<String, _>("priority");
+        let attempt      = row.get::<i64, _>("attempt");
+        let max_attempts = row.get::<Option<i64>, _>("max_attempts");
 
         let updated = sqlx::query(
             r"
-            UPDATE jobs 
+            UPDATE jobs
                 SET status = 'active',
-                    attempt = attempt + 1, 
-                    updated_at = ?1 
-                WHERE job_id = ?2 AND status = 'pending';
+                    attempt = attempt + 1,
+                    lease_expires_at = ?1,
+                    updated_at = ?2
+                WHERE job_id = ?3 AND status = 'pending';
             "
         )
+        .bind(now + lease_secs)
         .bind(now)
         .bind(job_id)
         .execute(&mut *tx)
@@ -436,16 +831,19 @@ impl Persistent {
         .rows_affected();
 
         if updated == 0 {
-            tx.rollback().await?; 
+            tx.rollback().await?;
             return Ok(None);
         }
-        
-        tx.commit().await?; 
+
+        tx.commit().await?;
 
         let kind = JobType::parse(&kind).ok_or_else(
             || CrawlerError::Parse("bad kind in DB".to_string())
         )?;
-        Ok(Some(Job { job_id, track_id, kind, attempt }))
+        let priority = JobPriority::parse(&priority).ok_or_else(
+            || CrawlerError::Parse("bad priority in DB".to_string())
+        )?;
+        Ok(Some(Job { job_id, track_id, kind, priority, attempt, max_attempts }))
     }
 
     pub async fn complete_job(&self, job_id: i64) -> Result<(), CrawlerError> {
@@ -457,74 +855,262 @@ impl Persistent {
         )
         .bind(Self::now())
         .bind(job_id)
-        .execute(&self.pool)
+        .execute(&self.write_pool)
         .await?; 
 
         Ok(())
     }
 
+    /// Marks a job `dead`: terminal, out of retries, no longer claimable.
+    /// Only called from `dead_letter_job` once it's recorded the failure.
     pub async fn fail_job(&self, job_id: i64, err: &str) -> Result<(), CrawlerError> {
         sqlx::query(
-            "UPDATE jobs SET status='failed', updated_at = ?1, 
+            "UPDATE jobs SET status='dead', updated_at = ?1,
                 last_error = ?2 WHERE job_id = ?3;"
         )
         .bind(Self::now())
-        .bind(err) 
+        .bind(err)
         .bind(job_id)
-        .execute(&self.pool)
-        .await?; 
+        .execute(&self.write_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Puts a transiently-failed job back to `pending`, invisible to
+    /// `claim_one_job` until `delay` has passed. `attempt` already tracks the
+    /// number of times the job has been claimed (bumped by `claim_one_job`
+    /// itself), so this only resets visibility and doesn't bump it again.
+    pub async fn requeue_job(&self, job_id: i64, delay: std::time::Duration) ->
+        Result<(), CrawlerError> {
+        let next_visible_at = Self::now() + delay.as_secs() as i64;
+        sqlx::query(
+            r"
+            UPDATE jobs SET status = 'pending', next_visible_at = ?1,
+                lease_expires_at = NULL, updated_at = ?2
+                WHERE job_id = ?3;
+            "
+        )
+        .bind(next_visible_at)
+        .bind(Self::now())
+        .bind(job_id)
+        .execute(&self.write_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Puts any `active` job of `kind` whose lease has lapsed back to
+    /// `pending`, immediately visible - a crashed worker's claim doesn't
+    /// strand the job forever. Returns the number reclaimed. `lease_secs`
+    /// is unused here (the lease recorded at claim time is already an
+    /// absolute deadline) but kept in the signature to mirror
+    /// `claim_one_job`'s, since callers pass the same config value to both.
+    pub async fn reclaim_expired_jobs(&self, kind: JobType, _lease_secs: i64) ->
+        Result<u64, CrawlerError> {
+        let now = Self::now();
+        let rows = sqlx::query(
+            r"
+            UPDATE jobs
+                SET status = 'pending', next_visible_at = 0,
+                    lease_expires_at = NULL, updated_at = ?1
+                WHERE kind = ?2 AND status = 'active' AND lease_expires_at < ?1;
+            "
+        )
+        .bind(now)
+        .bind(kind.as_str())
+        .execute(&self.write_pool)
+        .await?
+        .rows_affected();
+
+        Ok(rows)
+    }
 
+    /// Extends an `active` job's lease by `lease_secs` from now - called
+    /// periodically by a worker mid-job (feature extraction can outlast a
+    /// single lease) so `reclaim_expired_jobs` doesn't mistake it for dead.
+    pub async fn heartbeat_job(&self, job_id: i64, lease_secs: i64) ->
+        Result<(), CrawlerError> {
+        sqlx::query(
+            r"
+            UPDATE jobs SET lease_expires_at = ?1, updated_at = ?1
+                WHERE job_id = ?2 AND status = 'active';
+            "
+        )
+        .bind(Self::now() + lease_secs)
+        .bind(job_id)
+        .execute(&self.write_pool)
+        .await?;
         Ok(())
     }
 
-    pub async fn ensure_track(&self, track: &SpotifyTrack) -> 
+    /// Records a job's final, unrecoverable failure in `dead_letter` (fatal
+    /// error, or a transient one that burned through `job_max_attempts`) and
+    /// marks the job itself `dead` so it stops being claimed.
+    pub async fn dead_letter_job(&self, job: &Job, err: &str) -> Result<(), CrawlerError> {
+        sqlx::query(
+            r"
+            INSERT INTO dead_letter (track_id, kind, attempt, last_error, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5);
+            "
+        )
+        .bind(&job.track_id)
+        .bind(job.kind.as_str())
+        .bind(job.attempt)
+        .bind(err)
+        .bind(Self::now())
+        .execute(&self.write_pool)
+        .await?;
+
+        self.fail_job(job.job_id, err).await
+    }
+
+    /// Enqueues a `Link` job for a newly-discovered/re-seen track at
+    /// `Foreground` priority, so it drains ahead of any `Background` backlog
+    /// (e.g. one resume's `requeue_incomplete_tracks` left behind).
+    pub async fn ensure_track(&self, track: &SpotifyTrack) ->
         Result<String, CrawlerError> {
-        let (track_id, _) = self.upsert_track(track).await?; 
+        let (track_id, _) = self.upsert_track(track).await?;
         let linked: Option<i64> = sqlx::query_scalar(
             "SELECT linked_ok FROM tracks WHERE id = ?1;"
         )
         .bind(&track_id)
-        .fetch_optional(&self.pool)
-        .await? 
+        .fetch_optional(&self.read_pool)
+        .await?
         .flatten();
 
         if linked.unwrap_or(0) == 0 {
-            self.enqueue_job_if_missing(&track_id, JobType::Link).await?; 
+            self.enqueue_job_if_missing(&track_id, JobType::Link, JobPriority::Foreground, None).await?;
         }
         Ok(track_id)
     }
 
-    pub async fn enqueue_features(&self, track_id: &str) -> Result<(), CrawlerError> {
+    /// Enqueues the `Features` job that follows a completed `Link` job,
+    /// inheriting that job's priority so a foreground track stays foreground
+    /// through its whole pipeline.
+    pub async fn enqueue_features(&self, track_id: &str, priority: JobPriority) ->
+        Result<(), CrawlerError> {
         let linking_and_features: (i64, i64) = sqlx::query_as(
             "SELECT linked_ok, features_ok FROM tracks WHERE id = ?1;"
         )
         .bind(track_id)
-        .fetch_optional(&self.pool)
-        .await? 
+        .fetch_optional(&self.read_pool)
+        .await?
         .unwrap_or((0,0));
 
         if linking_and_features.0 == 1 && linking_and_features.1 == 0 {
-            self.enqueue_job_if_missing(track_id, JobType::Features).await?; 
+            self.enqueue_job_if_missing(track_id, JobType::Features, priority, None).await?;
         }
 
         Ok(())
     }
 
-    pub async fn get_track_metadata(&self, track_id: &str) -> 
+    /// Resets any job left `active` by a process that died mid-claim back to
+    /// `pending` so `--resume` doesn't leave it stuck forever. Returns how
+    /// many jobs were reset.
+    pub async fn reset_stuck_jobs(&self) -> Result<u64, CrawlerError> {
+        let result = sqlx::query(
+            "UPDATE jobs SET status = 'pending', updated_at = ?1 WHERE status = 'active';"
+        )
+        .bind(Self::now())
+        .execute(&self.write_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Re-enqueues a link/features job for any track missing one, covering
+    /// tracks whose job row was lost or never created before a crash. Queued
+    /// at `Background` priority: this is the "large backlog" a freshly
+    /// discovered track via `ensure_track` should jump ahead of. Returns
+    /// `(link_jobs_added, features_jobs_added)`.
+    pub async fn requeue_incomplete_tracks(&self) -> Result<(u64, u64), CrawlerError> {
+        let now = Self::now();
+
+        let link = sqlx::query(
+            r"
+            INSERT OR IGNORE INTO jobs (track_id, kind, status, priority, attempt, created_at, updated_at)
+            SELECT id, 'link', 'pending', 'background', 0, ?1, ?1 FROM tracks WHERE linked_ok = 0;
+            "
+        )
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?
+        .rows_affected();
+
+        let features = sqlx::query(
+            r"
+            INSERT OR IGNORE INTO jobs (track_id, kind, status, priority, attempt, created_at, updated_at)
+            SELECT id, 'features', 'pending', 'background', 0, ?1, ?1 FROM tracks
+             WHERE linked_ok = 1 AND features_ok = 0;
+            "
+        )
+        .bind(now)
+        .execute(&self.write_pool)
+        .await?
+        .rows_affected();
+
+        Ok((link, features))
+    }
+
+    /// Offset to resume a playlist/album seed walk from; 0 if no cursor is saved.
+    pub async fn get_seed_cursor(&self, kind: SeedKind, seed_id: &str) ->
+        Result<u32, CrawlerError> {
+        let offset: Option<i64> = sqlx::query_scalar(
+            "SELECT cursor_offset FROM seed_cursors WHERE kind = ?1 AND seed_id = ?2;"
+        )
+        .bind(kind.as_str())
+        .bind(seed_id)
+        .fetch_optional(&self.read_pool)
+        .await?;
+        Ok(offset.unwrap_or(0) as u32)
+    }
+
+    pub async fn set_seed_cursor(&self, kind: SeedKind, seed_id: &str, offset: u32) ->
+        Result<(), CrawlerError> {
+        sqlx::query(
+            r"
+            INSERT INTO seed_cursors (kind, seed_id, cursor_offset, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(kind, seed_id) DO UPDATE SET
+                cursor_offset = excluded.cursor_offset,
+                updated_at = excluded.updated_at;
+            "
+        )
+        .bind(kind.as_str())
+        .bind(seed_id)
+        .bind(offset as i64)
+        .bind(Self::now())
+        .execute(&self.write_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Drops a seed cursor once its walk finishes, so a later re-seed of the
+    /// same playlist/album starts from the top again instead of resuming.
+    pub async fn clear_seed_cursor(&self, kind: SeedKind, seed_id: &str) ->
+        Result<(), CrawlerError> {
+        sqlx::query("DELETE FROM seed_cursors WHERE kind = ?1 AND seed_id = ?2;")
+            .bind(kind.as_str())
+            .bind(seed_id)
+            .execute(&self.write_pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_track_metadata(&self, track_id: &str) ->
         Result<Option<Track>, CrawlerError> {
         let row = sqlx::query(
             r"
-            SELECT id, spotify_id, title, artist_all, isrc, mb_recording_id, 
-                linked_ok, features_ok,
+            SELECT id, spotify_id, title, artist_all, isrc, mb_recording_id,
+                linked_ok, features_ok, duration_ms, fingerprint,
             updated_at
                 FROM tracks where id = ?1;
             "
         )
         .bind(track_id)
-        .fetch_optional(&self.pool)
-        .await?; 
+        .fetch_optional(&self.read_pool)
+        .await?;
 
-        Ok(row.map(|r| { 
+        Ok(row.map(|r| {
             let artist_all_json: Option<String> = r.try_get("artist_all").ok();
             let artist_all: Vec<String> = artist_all_json
                 .as_deref()
@@ -539,7 +1125,9 @@ impl Persistent {
                 isrc: r.try_get("isrc").ok(),
                 mb_recording_id: r.try_get("mb_recording_id").ok(),
                 linked_ok: r.get::<i64, _>("linked_ok") == 1,
-                features_ok: r.get::<i64, _>("features_ok") == 1, 
+                features_ok: r.get::<i64, _>("features_ok") == 1,
+                duration_ms: r.try_get("duration_ms").ok(),
+                fingerprint: r.try_get("fingerprint").ok(),
                 updated_at: r.get("updated_at")
             }
         }))
@@ -559,7 +1147,7 @@ impl Persistent {
         .bind(key)
         .bind(rel_path)
         .bind(Self::now())
-        .execute(&self.pool)
+        .execute(&self.write_pool)
         .await?;
         Ok(())
     }
@@ -570,7 +1158,7 @@ impl Persistent {
         source: &str,
         items: &[(String, f64)],
     ) -> Result<(), CrawlerError> {
-        let mut tx = self.pool.begin().await?;
+        let mut tx = self.write_pool.begin().await?;
         for (feature, value) in items {
             sqlx::query(r"
                 INSERT INTO features (
@@ -599,7 +1187,7 @@ impl Persistent {
         source: &str,
         items: &[(String, String)],
     ) -> Result<(), CrawlerError> {
-        let mut tx = self.pool.begin().await?;
+        let mut tx = self.write_pool.begin().await?;
         for (feature, value) in items {
             sqlx::query(r"
                 INSERT INTO features (
@@ -620,4 +1208,126 @@ impl Persistent {
         tx.commit().await?;
         Ok(())
     }
+
+    /// Builds a dense, z-score-normalized `FeatureMatrix` from the sparse
+    /// `features` table, restricted to `dtype = 'num'` rows whose `source`
+    /// is in `sources`. Column order is the distinct `(source, feature)`
+    /// set sorted ascending, so it's stable across calls against the same
+    /// data. Missing cells are imputed to the column mean (0 once
+    /// normalized); a column with zero variance is left at 0 rather than
+    /// dividing by a zero std.
+    pub async fn export_feature_matrix(&self, sources: &[&str]) ->
+        Result<FeatureMatrix, CrawlerError> {
+        if sources.is_empty() {
+            return Ok(FeatureMatrix { track_ids: Vec::new(), columns: Vec::new(), values: Vec::new() });
+        }
+
+        let mut columns_qb = sqlx::QueryBuilder::new(
+            "SELECT DISTINCT source, feature FROM features WHERE dtype = 'num' AND source IN ("
+        );
+        {
+            let mut sep = columns_qb.separated(", ");
+            for source in sources {
+                sep.push_bind(*source);
+            }
+        }
+        columns_qb.push(") ORDER BY source, feature;");
+
+        let columns: Vec<(String, String)> = columns_qb.build()
+            .fetch_all(&self.read_pool)
+            .await?
+            .into_iter()
+            .map(|row| (row.get::<String, _>("source"), row.get::<String, _>("feature")))
+            .collect();
+
+        if columns.is_empty() {
+            return Ok(FeatureMatrix { track_ids: Vec::new(), columns, values: Vec::new() });
+        }
+
+        let mut tracks_qb = sqlx::QueryBuilder::new(
+            "SELECT DISTINCT track_id FROM features WHERE dtype = 'num' AND source IN ("
+        );
+        {
+            let mut sep = tracks_qb.separated(", ");
+            for source in sources {
+                sep.push_bind(*source);
+            }
+        }
+        tracks_qb.push(") ORDER BY track_id;");
+
+        let track_ids: Vec<String> = tracks_qb.build()
+            .fetch_all(&self.read_pool)
+            .await?
+            .into_iter()
+            .map(|row| row.get::<String, _>("track_id"))
+            .collect();
+
+        if track_ids.is_empty() {
+            return Ok(FeatureMatrix { track_ids, columns, values: Vec::new() });
+        }
+
+        let mut cells_qb = sqlx::QueryBuilder::new(
+            "SELECT track_id, source, feature, num_value FROM features \
+             WHERE dtype = 'num' AND source IN ("
+        );
+        {
+            let mut sep = cells_qb.separated(", ");
+            for source in sources {
+                sep.push_bind(*source);
+            }
+        }
+        cells_qb.push(");");
+
+        let mut cells: HashMap<(String, String, String), f64> = HashMap::new();
+        for row in cells_qb.build().fetch_all(&self.read_pool).await? {
+            let track_id: String = row.get("track_id");
+            let source: String = row.get("source");
+            let feature: String = row.get("feature");
+            let value: f64 = row.get("num_value");
+            cells.insert((track_id, source, feature), value);
+        }
+
+        let n_cols = columns.len();
+        let mut values = vec![f64::NAN; track_ids.len() * n_cols];
+        for (r, track_id) in track_ids.iter().enumerate() {
+            for (c, (source, feature)) in columns.iter().enumerate() {
+                let key = (track_id.clone(), source.clone(), feature.clone());
+                if let Some(v) = cells.get(&key) {
+                    values[r * n_cols + c] = *v;
+                }
+            }
+        }
+
+        for c in 0..n_cols {
+            let present: Vec<f64> = (0..track_ids.len())
+                .filter_map(|r| {
+                    let v = values[r * n_cols + c];
+                    (!v.is_nan()).then_some(v)
+                })
+                .collect();
+
+            let mean = if present.is_empty() {
+                0.0
+            } else {
+                present.iter().sum::<f64>() / present.len() as f64
+            };
+            let variance = if present.is_empty() {
+                0.0
+            } else {
+                present.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / present.len() as f64
+            };
+            let std = variance.sqrt();
+
+            for r in 0..track_ids.len() {
+                let cell = &mut values[r * n_cols + c];
+                *cell = if cell.is_nan() || std == 0.0 {
+                    0.0
+                } else {
+                    (*cell - mean) / std
+                };
+            }
+        }
+
+        Ok(FeatureMatrix { track_ids, columns, values })
+    }
 }
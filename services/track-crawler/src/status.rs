@@ -0,0 +1,300 @@
+//!
+//! src/status.rs  Andrew Belles  Sept 16th, 2025
+//!
+//! Minimal HTTP surface for observing and nudging a running crawl:
+//! pipeline-wide counters, per-client request/429 counts, and lookup-cache
+//! hit/miss counts at `GET /status`, and `POST /submit/<track_id>` to bump a
+//! specific track's pending job to `Foreground` priority and await its
+//! outcome. Hand-rolled over a raw `TcpListener` rather than pulling in a
+//! web framework, since these are the only two routes the crawler needs to
+//! expose.
+//!
+//!
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, Mutex};
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::cache::Cache;
+use crate::errors::CrawlerError;
+use crate::persistent::{JobStatus, JobType, Persistent};
+
+/// Per-client request accounting, incremented inside `fetch::send_with_retry`.
+#[derive(Debug, Default)]
+pub struct ClientStats {
+    pub requests: AtomicU64,
+    pub rate_limited: AtomicU64,
+    pub retries: AtomicU64,
+}
+
+impl ClientStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn snapshot(&self) -> ClientStatsSnapshot {
+        ClientStatsSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ClientStatsSnapshot {
+    requests: u64,
+    rate_limited: u64,
+    retries: u64,
+}
+
+/// One `ClientStats` handle per `fetch` client, grouped for the `/status` response.
+#[derive(Clone)]
+pub struct ClientsStats {
+    pub spotify: Arc<ClientStats>,
+    pub musicbrainz: Arc<ClientStats>,
+    pub acousticbrainz: Arc<ClientStats>,
+    pub lastfm: Arc<ClientStats>,
+    pub acoustid: Arc<ClientStats>,
+}
+
+/// The `cache::Cache` handles consulted by the link/features loops, grouped
+/// for the `/status` response the same way `ClientsStats` groups the HTTP
+/// clients.
+#[derive(Clone)]
+pub struct CachesStats {
+    pub mbid: Arc<Cache<String>>,
+    pub feature: Arc<Cache<serde_json::Value>>,
+}
+
+/// Pipeline-wide counters, incremented at each crawl stage in `crawler.rs`.
+#[derive(Debug, Default)]
+pub struct PipelineCounters {
+    pub tracks_discovered: AtomicU64,
+    pub tracks_upserted: AtomicU64,
+    pub linked_ok: AtomicU64,
+    pub features_ok: AtomicU64,
+}
+
+impl PipelineCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Result delivered to a `submit_foreground` caller once the track's pending
+/// job clears (or is permanently dead-lettered).
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", content = "error", rename_all = "snake_case")]
+pub enum JobOutcome {
+    /// Linking and feature extraction both succeeded.
+    Complete,
+    /// Linked, but feature extraction was dead-lettered.
+    Partial(String),
+    /// Linking itself was dead-lettered.
+    Failed(String),
+}
+
+/// One-shot result senders for in-flight `submit_foreground` calls, keyed by
+/// track id. `Crawler::notify_outcome` fires and removes the matching entry
+/// when a job is dead-lettered or a features job completes. The job queue itself stays entirely
+/// in `persistent.rs`'s sqlite tables; this map only carries "tell me when
+/// it's done".
+pub type NotifyMap = Arc<Mutex<HashMap<String, oneshot::Sender<JobOutcome>>>>;
+
+/// Bumps `track_id`'s pending link (or, if already linked, features) job to
+/// `Foreground` priority and registers a receiver that resolves once the
+/// track's pipeline settles: linking dead-lettered, or the features job
+/// completed or dead-lettered. A link job finishing does not resolve it.
+/// Called from the `POST /submit` route below.
+pub async fn submit_foreground(
+    db: &Persistent,
+    notify: &NotifyMap,
+    track_id: &str,
+) -> Result<oneshot::Receiver<JobOutcome>, CrawlerError> {
+    let meta = db.get_track_metadata(track_id).await?
+        .ok_or_else(|| CrawlerError::NotFound("track not found in db".into()))?;
+
+    let kind = if meta.linked_ok { JobType::Features } else { JobType::Link };
+    db.bump_priority(track_id, kind).await?;
+
+    let (tx, rx) = oneshot::channel();
+    notify.lock().await.insert(track_id.to_string(), tx);
+    Ok(rx)
+}
+
+#[derive(Debug, Serialize)]
+struct StatusSnapshot {
+    tracks_discovered: u64,
+    tracks_upserted: u64,
+    linked_ok: u64,
+    features_ok: u64,
+    queue_depth: QueueDepth,
+    clients: ClientsSnapshot,
+    caches: CachesSnapshot,
+}
+
+#[derive(Debug, Serialize)]
+struct QueueDepth {
+    link_pending: i64,
+    features_pending: i64,
+    /// Total jobs of either kind that burned through `job_max_attempts`
+    /// (or hit a `Fatal` error) and are sitting in `dead_letter`.
+    dead: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct ClientsSnapshot {
+    spotify: ClientStatsSnapshot,
+    musicbrainz: ClientStatsSnapshot,
+    acousticbrainz: ClientStatsSnapshot,
+    lastfm: ClientStatsSnapshot,
+    acoustid: ClientStatsSnapshot,
+}
+
+#[derive(Debug, Serialize)]
+struct CachesSnapshot {
+    mbid: crate::cache::CacheStats,
+    feature: crate::cache::CacheStats,
+}
+
+async fn build_snapshot(
+    counters: &PipelineCounters,
+    clients: &ClientsStats,
+    caches: &CachesStats,
+    db: &Persistent,
+) -> Result<StatusSnapshot, CrawlerError> {
+    let link_pending = db.count_jobs(JobType::Link, JobStatus::Pending).await?;
+    let features_pending = db.count_jobs(JobType::Features, JobStatus::Pending).await?;
+    let dead = db.count_jobs(JobType::Link, JobStatus::Dead).await?
+        + db.count_jobs(JobType::Features, JobStatus::Dead).await?;
+
+    Ok(StatusSnapshot {
+        tracks_discovered: counters.tracks_discovered.load(Ordering::Relaxed),
+        tracks_upserted: counters.tracks_upserted.load(Ordering::Relaxed),
+        linked_ok: counters.linked_ok.load(Ordering::Relaxed),
+        features_ok: counters.features_ok.load(Ordering::Relaxed),
+        queue_depth: QueueDepth { link_pending, features_pending, dead },
+        clients: ClientsSnapshot {
+            spotify: clients.spotify.snapshot(),
+            musicbrainz: clients.musicbrainz.snapshot(),
+            acousticbrainz: clients.acousticbrainz.snapshot(),
+            lastfm: clients.lastfm.snapshot(),
+            acoustid: clients.acoustid.snapshot(),
+        },
+        caches: CachesSnapshot {
+            mbid: caches.mbid.snapshot(),
+            feature: caches.feature.snapshot(),
+        },
+    })
+}
+
+/// How long `POST /submit/<track_id>` waits on the job outcome before giving
+/// up and returning 504 - the connection stays open, but a stuck foreground
+/// job shouldn't be able to wedge this handler forever.
+const SUBMIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+async fn handle_submit(db: &Persistent, notify: &NotifyMap, track_id: &str) -> String {
+    let rx = match submit_foreground(db, notify, track_id).await {
+        Ok(rx) => rx,
+        Err(CrawlerError::NotFound(_)) =>
+            return "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        Err(_) =>
+            return "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+
+    match timeout(SUBMIT_TIMEOUT, rx).await {
+        Ok(Ok(outcome)) => {
+            let body = serde_json::to_string(&outcome).unwrap_or_else(|_| "{}".to_string());
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(), body
+            )
+        }
+        Ok(Err(_)) =>
+            "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        Err(_) =>
+            "HTTP/1.1 504 Gateway Timeout\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    }
+}
+
+async fn handle_connection(
+    mut stream: tokio::net::TcpStream,
+    counters: Arc<PipelineCounters>,
+    clients: ClientsStats,
+    caches: CachesStats,
+    notify: NotifyMap,
+    db: Arc<Persistent>,
+) {
+    let mut buf = [0_u8; 1024];
+    let n = match stream.read(&mut buf).await {
+        Ok(n) => n,
+        Err(_) => return,
+    };
+    let request = String::from_utf8_lossy(&buf[..n]);
+
+    let response = if request.starts_with("GET /status ") || request.starts_with("GET /status\r") {
+        match build_snapshot(&counters, &clients, &caches, &db).await {
+            Ok(snapshot) => {
+                let body = serde_json::to_string(&snapshot).unwrap_or_else(|_| "{}".to_string());
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(), body
+                )
+            }
+            Err(_) => "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        }
+    } else if let Some(track_id) = request.strip_prefix("POST /submit/")
+        .and_then(|rest| rest.split_whitespace().next()) {
+        handle_submit(&db, &notify, track_id).await
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+
+    let _ = stream.write_all(response.as_bytes()).await;
+}
+
+/// Serves `GET /status` and `POST /submit/<track_id>` on `addr` until
+/// `shutdown` is cancelled. Any other path/method gets a 404 — this is an
+/// internal observability/control endpoint, not a general-purpose HTTP server.
+pub async fn serve(
+    addr: SocketAddr,
+    counters: Arc<PipelineCounters>,
+    clients: ClientsStats,
+    caches: CachesStats,
+    notify: NotifyMap,
+    db: Arc<Persistent>,
+    shutdown: CancellationToken,
+) -> Result<(), CrawlerError> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| CrawlerError::Config(format!("status bind {addr}: {e}")))?;
+    info!(%addr, "status.listen");
+
+    loop {
+        tokio::select! {
+            () = shutdown.cancelled() => break,
+            accepted = listener.accept() => {
+                let (stream, _) = match accepted {
+                    Ok(v) => v,
+                    Err(e) => { warn!(error = ?e, "status.accept"); continue; }
+                };
+                tokio::spawn(handle_connection(
+                    stream, counters.clone(), clients.clone(), caches.clone(), notify.clone(), db.clone()
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}